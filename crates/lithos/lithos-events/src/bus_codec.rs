@@ -0,0 +1,109 @@
+//! `BusCodec` implementations for this crate's wire types.
+//!
+//! [`lithos_icc::BusCodec`] is what [`lithos_icc::Journal`] uses to persist
+//! bus items to disk. The encoding here is a plain little-endian field dump —
+//! deliberately not a raw transmute of the `#[repr(C, packed)]` in-memory
+//! layout, since this crate forbids unsafe code and packed-field references
+//! aren't safe to take anyway.
+
+use crate::{SymbolId, TopOfBook};
+use lithos_icc::BusCodec;
+use std::io::{self, Write};
+
+impl BusCodec for SymbolId {
+    const ENCODED_LEN: usize = 2;
+
+    fn encode(&self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(&self.0.to_le_bytes())
+    }
+
+    fn decode(buf: &[u8]) -> Option<(Self, usize)> {
+        if buf.len() < Self::ENCODED_LEN {
+            return None;
+        }
+        let id = u16::from_le_bytes(buf[0..2].try_into().ok()?);
+        Some((SymbolId(id), Self::ENCODED_LEN))
+    }
+}
+
+impl BusCodec for TopOfBook {
+    const ENCODED_LEN: usize = 8 + SymbolId::ENCODED_LEN + 8 + 8 + 8 + 8;
+
+    fn encode(&self, out: &mut impl Write) -> io::Result<()> {
+        // Copy every field out of the packed struct before touching it;
+        // taking a reference to a packed field is unsound.
+        let ts_event_ns = self.ts_event_ns;
+        let symbol_id = self.symbol_id;
+        let bid_px_ticks = self.bid_px_ticks;
+        let bid_qty_lots = self.bid_qty_lots;
+        let ask_px_ticks = self.ask_px_ticks;
+        let ask_qty_lots = self.ask_qty_lots;
+
+        out.write_all(&ts_event_ns.to_le_bytes())?;
+        symbol_id.encode(out)?;
+        out.write_all(&bid_px_ticks.to_le_bytes())?;
+        out.write_all(&bid_qty_lots.to_le_bytes())?;
+        out.write_all(&ask_px_ticks.to_le_bytes())?;
+        out.write_all(&ask_qty_lots.to_le_bytes())
+    }
+
+    fn decode(buf: &[u8]) -> Option<(Self, usize)> {
+        if buf.len() < Self::ENCODED_LEN {
+            return None;
+        }
+        let ts_event_ns = u64::from_le_bytes(buf[0..8].try_into().ok()?);
+        let (symbol_id, _) = SymbolId::decode(&buf[8..10])?;
+        let bid_px_ticks = i64::from_le_bytes(buf[10..18].try_into().ok()?);
+        let bid_qty_lots = i64::from_le_bytes(buf[18..26].try_into().ok()?);
+        let ask_px_ticks = i64::from_le_bytes(buf[26..34].try_into().ok()?);
+        let ask_qty_lots = i64::from_le_bytes(buf[34..42].try_into().ok()?);
+
+        Some((
+            TopOfBook {
+                ts_event_ns,
+                symbol_id,
+                bid_px_ticks,
+                bid_qty_lots,
+                ask_px_ticks,
+                ask_qty_lots,
+            },
+            Self::ENCODED_LEN,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_of_book_round_trips_through_bus_codec() {
+        let tob = TopOfBook {
+            ts_event_ns: 1_700_000_000_000_000_000,
+            symbol_id: SymbolId(42),
+            bid_px_ticks: 12345,
+            bid_qty_lots: 1500,
+            ask_px_ticks: 12346,
+            ask_qty_lots: 2300,
+        };
+
+        let mut buf = Vec::new();
+        tob.encode(&mut buf).unwrap();
+        assert_eq!(buf.len(), TopOfBook::ENCODED_LEN);
+
+        let (decoded, consumed) = TopOfBook::decode(&buf).unwrap();
+        assert_eq!(consumed, TopOfBook::ENCODED_LEN);
+        assert_eq!(decoded.ts_event_ns, tob.ts_event_ns);
+        assert_eq!(decoded.symbol_id, tob.symbol_id);
+        assert_eq!(decoded.bid_px_ticks, tob.bid_px_ticks);
+        assert_eq!(decoded.bid_qty_lots, tob.bid_qty_lots);
+        assert_eq!(decoded.ask_px_ticks, tob.ask_px_ticks);
+        assert_eq!(decoded.ask_qty_lots, tob.ask_qty_lots);
+    }
+
+    #[test]
+    fn decode_rejects_truncated_buffer() {
+        let buf = [0u8; 10];
+        assert!(TopOfBook::decode(&buf).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+mod bus_codec;
 pub mod top_of_the_book;
 pub use top_of_the_book::{SymbolId, TopOfBook};
 
@@ -11,3 +12,22 @@ pub use top_of_the_book::{SymbolId, TopOfBook};
 pub enum Event {
     TopOfBook(TopOfBook),
 }
+
+/// Number of `Event` variants. Sized for a fixed-size handler table indexed
+/// by `Event::kind()` (see `onyx_engine::OnyxEngineBuilder`), so dispatch
+/// stays a direct array index with no per-event branching.
+pub const EVENT_KIND_COUNT: usize = 1;
+
+/// Index of the `TopOfBook` variant into a table sized `EVENT_KIND_COUNT`.
+pub const EVENT_KIND_TOP_OF_BOOK: usize = 0;
+
+impl Event {
+    /// This event's discriminant as a dense `0..EVENT_KIND_COUNT` index,
+    /// for use as a handler-table lookup key.
+    #[inline]
+    pub fn kind(&self) -> usize {
+        match self {
+            Event::TopOfBook(_) => EVENT_KIND_TOP_OF_BOOK,
+        }
+    }
+}
@@ -27,6 +27,76 @@ impl TopOfBook {
     }
 }
 
+/// Computes 10^DP at compile time. Mirrors the helper of the same name used
+/// on the parsing side (`obsidian_util::floating_parse`), kept local here so
+/// this crate doesn't take on a dependency just to format its own struct.
+#[inline(always)]
+const fn pow10<const DP: u32>() -> i64 {
+    match DP {
+        0 => 1,
+        1 => 10,
+        2 => 100,
+        3 => 1000,
+        4 => 10_000,
+        5 => 100_000,
+        6 => 1_000_000,
+        _ => 10_i64.pow(DP),
+    }
+}
+
+/// Formats a fixed-point integer (as produced by `parse_fixed_dp` on the
+/// ingestion side) back into a decimal string: sign, integer part, `.`, and
+/// exactly `DP` zero-padded fractional digits. Writes directly into `out`
+/// with no heap allocation, so it's usable from `#![no_std]`-style hot paths.
+///
+/// Round-trips exactly for every value `parse_fixed_dp::<DP>` can produce.
+pub fn format_fixed_dp<const DP: u32>(
+    value: i64,
+    out: &mut impl core::fmt::Write,
+) -> core::fmt::Result {
+    let scale = pow10::<DP>();
+    let sign = if value < 0 { "-" } else { "" };
+    let magnitude = value.unsigned_abs();
+    let int_part = magnitude / scale as u64;
+    let frac_part = magnitude % scale as u64;
+    write!(out, "{sign}{int_part}.{frac_part:0width$}", width = DP as usize)
+}
+
+/// Formats a price tick value (2 decimal places). Inverse of `parse_px_2dp`.
+#[inline]
+pub fn format_px_2dp(value: i64, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+    format_fixed_dp::<2>(value, out)
+}
+
+/// Formats a quantity lot value (3 decimal places). Inverse of `parse_qty_3dp`.
+#[inline]
+pub fn format_qty_3dp(value: i64, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+    format_fixed_dp::<3>(value, out)
+}
+
+impl core::fmt::Display for TopOfBook {
+    /// Prints the full bid/ask quote as human-readable decimals, e.g.
+    /// `SYM(1) bid=123.45@1.500 ask=123.46@2.300`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Copy out of the packed struct first: taking a reference to a
+        // packed field is unsound, so locals are required before formatting.
+        let symbol_id = self.symbol_id;
+        let bid_px = self.bid_px_ticks;
+        let bid_qty = self.bid_qty_lots;
+        let ask_px = self.ask_px_ticks;
+        let ask_qty = self.ask_qty_lots;
+
+        write!(f, "SYM({}) bid=", symbol_id.0)?;
+        format_px_2dp(bid_px, f)?;
+        write!(f, "@")?;
+        format_qty_3dp(bid_qty, f)?;
+        write!(f, " ask=")?;
+        format_px_2dp(ask_px, f)?;
+        write!(f, "@")?;
+        format_qty_3dp(ask_qty, f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,4 +135,34 @@ mod tests {
     fn symbol_id_is_pod() {
         assert_eq!(size_of::<SymbolId>(), 2);
     }
+
+    /// format_fixed_dp is the inverse of the parser: it should always emit
+    /// exactly DP fractional digits, zero-padded, with the sign up front.
+    #[test]
+    fn format_fixed_dp_round_trips_expected_strings() {
+        let mut s = String::new();
+        format_px_2dp(12345, &mut s).unwrap();
+        assert_eq!(s, "123.45");
+
+        let mut s = String::new();
+        format_qty_3dp(12300, &mut s).unwrap();
+        assert_eq!(s, "12.300");
+
+        let mut s = String::new();
+        format_px_2dp(-5, &mut s).unwrap();
+        assert_eq!(s, "-0.05");
+    }
+
+    #[test]
+    fn top_of_book_display_renders_full_quote() {
+        let tob = TopOfBook {
+            ts_event_ns: 0,
+            symbol_id: SymbolId(1),
+            bid_px_ticks: 12345,
+            bid_qty_lots: 1500,
+            ask_px_ticks: 12346,
+            ask_qty_lots: 2300,
+        };
+        assert_eq!(tob.to_string(), "SYM(1) bid=123.45@1.500 ask=123.46@2.300");
+    }
 }
@@ -0,0 +1,178 @@
+//! TCP fan-out bridge: distributes a local broadcast ring to remote hosts.
+//!
+//! [`BridgeServer`] opens a `BroadcastReader<T>` against a local ring and, for
+//! every TCP subscriber that connects, spawns a thread that drains its own
+//! independent `BroadcastReader` (the ring already supports any number of
+//! concurrent readers — one per subscriber is simplest, and means a slow
+//! subscriber's socket backpressure can never stall the others) and frames
+//! each item onto the socket. [`BridgeReader`] is the far side: it reads
+//! frames back and republishes into a local ring, exactly like
+//! [`crate::journal::JournalReader`] does for a file.
+//!
+//! # Wire format
+//! Each frame is, little-endian:
+//! `[magic: u32][seq: u64][len: u32][len bytes of BusCodec::encode output]`.
+//! `seq` increments once per frame sent, and additionally skips ahead by
+//! however many items the server's own reader lost to ring overruns before
+//! that frame; a `BridgeReader` that sees a gap
+//! reports it the same way a `BroadcastReader` reports an overrun, since
+//! both represent "the consumer missed some events".
+
+use crate::BroadcastWriter;
+use crate::broadcast::BroadcastReader;
+use crate::codec::BusCodec;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::path::Path;
+
+/// Magic bytes identifying a bridge frame ("LTBRIDGE" truncated to 4 bytes).
+const BRIDGE_MAGIC: u32 = 0x4C54_4252;
+
+/// Upper bound on a single frame's payload length. Real frames are at most
+/// a few hundred bytes; this only exists so a corrupted or malicious remote
+/// peer can't force a ~4GB allocation per frame via the wire `len` field.
+const MAX_FRAME_PAYLOAD_LEN: usize = 64 * 1024 * 1024;
+
+/// Distributes a local ring to any number of TCP subscribers.
+pub struct BridgeServer {
+    listener: TcpListener,
+    ring_path: String,
+}
+
+impl BridgeServer {
+    /// Binds `addr` and prepares to serve the ring at `ring_path` to
+    /// subscribers as they connect.
+    pub fn bind<A: ToSocketAddrs, P: AsRef<Path>>(addr: A, ring_path: P) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            ring_path: ring_path.as_ref().to_string_lossy().into_owned(),
+        })
+    }
+
+    /// Accepts subscribers forever, spawning one relay thread per connection.
+    ///
+    /// Never returns except on a fatal `accept` error; a single subscriber's
+    /// socket error only tears down its own relay thread.
+    pub fn serve<T>(&self) -> io::Result<()>
+    where
+        T: Copy + BusCodec + Send + 'static,
+    {
+        loop {
+            let (socket, _addr) = self.listener.accept()?;
+            let ring_path = self.ring_path.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = Self::relay_one::<T>(&ring_path, socket) {
+                    eprintln!("bridge subscriber relay ended: {e}");
+                }
+            });
+        }
+    }
+
+    /// Relays every event published after this subscriber connects, exactly
+    /// like `BroadcastReader`'s own tail-follow semantics.
+    fn relay_one<T: Copy + BusCodec>(ring_path: &str, mut socket: TcpStream) -> io::Result<()> {
+        let mut reader = BroadcastReader::<T>::open(ring_path)?;
+        socket.set_nodelay(true).ok();
+        let mut seq: u64 = 0;
+        let mut buf = Vec::with_capacity(T::ENCODED_LEN);
+
+        loop {
+            let before = reader.overruns();
+            let Some(value) = reader.read_blocking(None) else {
+                return Ok(());
+            };
+            // Items this reader was lapped past never reach the socket;
+            // skipping `seq` over them surfaces them as a gap remotely.
+            seq += reader.overruns() - before;
+
+            buf.clear();
+            value.encode(&mut buf)?;
+
+            socket.write_all(&BRIDGE_MAGIC.to_le_bytes())?;
+            socket.write_all(&seq.to_le_bytes())?;
+            socket.write_all(&(buf.len() as u32).to_le_bytes())?;
+            socket.write_all(&buf)?;
+
+            seq += 1;
+        }
+    }
+}
+
+/// Reconstructs a remote ring's stream locally, republishing into a local
+/// `BroadcastWriter`.
+pub struct BridgeReader {
+    socket: TcpStream,
+    next_seq: u64,
+    /// Count of frame sequence gaps detected (remote overruns, or dropped
+    /// frames), mirroring `BroadcastReader::overruns()`.
+    gaps: u64,
+}
+
+impl BridgeReader {
+    /// Connects to a `BridgeServer` at `addr`.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let socket = TcpStream::connect(addr)?;
+        socket.set_nodelay(true).ok();
+        Ok(Self {
+            socket,
+            next_seq: 0,
+            gaps: 0,
+        })
+    }
+
+    /// Count of sequence gaps observed since this reader connected.
+    pub fn gaps(&self) -> u64 {
+        self.gaps
+    }
+
+    /// Reads one frame and republishes it into `writer`, blocking until a
+    /// frame arrives or the connection closes.
+    ///
+    /// Returns `Ok(false)` on a clean connection close, `Ok(true)` after a
+    /// successful publish.
+    pub fn relay_one<T: Copy + BusCodec>(&mut self, writer: &mut BroadcastWriter<T>) -> io::Result<bool> {
+        let mut magic_buf = [0u8; 4];
+        match self.socket.read_exact(&mut magic_buf) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+            Err(e) => return Err(e),
+        }
+        if u32::from_le_bytes(magic_buf) != BRIDGE_MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "bad bridge frame magic"));
+        }
+
+        let mut seq_buf = [0u8; 8];
+        self.socket.read_exact(&mut seq_buf)?;
+        let seq = u64::from_le_bytes(seq_buf);
+        if seq != self.next_seq {
+            self.gaps += seq.saturating_sub(self.next_seq);
+        }
+        self.next_seq = seq + 1;
+
+        let mut len_buf = [0u8; 4];
+        self.socket.read_exact(&mut len_buf)?;
+        let len = u32::from_le_bytes(len_buf) as usize;
+        if len > MAX_FRAME_PAYLOAD_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "bridge frame length exceeds sanity cap",
+            ));
+        }
+
+        let mut payload = vec![0u8; len];
+        self.socket.read_exact(&mut payload)?;
+
+        let (value, _) = T::decode(&payload)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed bridge frame"))?;
+        writer.publish(value);
+
+        Ok(true)
+    }
+
+    /// Relays frames forever, republishing each into `writer`, until the
+    /// connection closes cleanly.
+    pub fn run<T: Copy + BusCodec>(&mut self, writer: &mut BroadcastWriter<T>) -> io::Result<()> {
+        while self.relay_one(writer)? {}
+        Ok(())
+    }
+}
@@ -17,10 +17,19 @@
 //!   threads. For multiple producers, open the ring from each thread (or process)
 //!   to get a separate `BroadcastWriter` per producer.
 //! - `BroadcastReader` is `Send` but not `Sync` (each reader is independent).
+//! - [`MultiWriter`] is `Send + Sync`: a single instance can be shared (typically
+//!   behind an `Arc`) across producer threads within one process, each calling
+//!   `publish(&self, ...)` directly rather than opening a separate mmap handle
+//!   per thread. It claims slots the same way `BroadcastWriter` does — so this
+//!   isn't a new concurrency model, just a shared-handle convenience over the
+//!   same `write_seq.fetch_add` claim.
 
-use crate::ring::{RingConfig, apply_overrun_policy, seq_to_index};
+use crate::futex;
+use crate::ring::{RateLimit, RingConfig, apply_overrun_policy, seq_to_index};
 use crate::seqlock::SeqlockSlot;
-use crate::shm_layout::{RING_MAGIC, RING_VERSION, RingHeader, bytes_for_ring};
+use crate::shm_layout::{
+    ConsumerScan, RING_MAGIC, RING_VERSION, ReaderLag, RingHeader, bytes_for_ring, now_ns,
+};
 use lithos_mmap::{MmapFile, MmapFileMut};
 use std::io;
 use std::marker::PhantomData;
@@ -28,6 +37,7 @@ use std::mem::size_of;
 use std::path::Path;
 use std::ptr;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
 /// The writer side of a broadcast ring buffer.
 ///
@@ -47,10 +57,103 @@ pub struct BroadcastWriter<T: Copy> {
     slots_base: *mut SeqlockSlot<T>,
     /// Bitmask for fast modulo: `index = seq & mask` (capacity must be power of 2).
     mask: u64,
+    /// Cached from the header at open/create time: whether `try_publish`
+    /// enforces back-pressure against registered readers.
+    bounded: bool,
+    /// Token-bucket ceiling for `publish_paced`/`try_publish_paced`. `None`
+    /// means unlimited (those methods just call `publish()`). This is
+    /// process-local state: pacing is a policy each producer instance
+    /// applies to its own publishing, not something shared via the header.
+    rate_limit: Option<RateLimit>,
+    /// Tokens currently available in the bucket. Only meaningful when
+    /// `rate_limit` is `Some`.
+    tokens: f64,
+    /// `now_ns()` at the last refill, used to compute elapsed time for the
+    /// next refill.
+    last_refill_ns: u64,
     /// Marker to tie the struct to type `T` without storing a `T`.
     _pd: PhantomData<T>,
 }
 
+/// Returned by [`BroadcastWriter::try_publish`] when publishing would lap a
+/// registered reader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backpressure;
+
+impl std::fmt::Display for Backpressure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("publish would lap a registered reader")
+    }
+}
+
+impl std::error::Error for Backpressure {}
+
+/// Returned by [`BroadcastWriter::try_publish_paced`] when the token bucket
+/// is empty and publishing would exceed the configured rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimited;
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("publish would exceed the configured rate limit")
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Outcome of [`BroadcastReader::try_read_outcome`].
+///
+/// Unlike `try_read`, which silently fast-forwards a lapped reader and
+/// hands back whatever item it lands on, this distinguishes "caught a gap"
+/// from "read an item in sequence" so a caller can surface the gap (e.g.
+/// log a warning) instead of processing a skipped-to element as if nothing
+/// happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadOutcome<T> {
+    /// An item was read with no gap since the previous read.
+    Item(T),
+    /// No new item is available; the reader is caught up with the writer.
+    Empty,
+    /// The writer lapped this reader: `skipped` items were overwritten
+    /// before they could be read. The reader's cursor has already been
+    /// fast-forwarded to the oldest item still in the ring; the next call
+    /// to `try_read_outcome` returns it as a normal `Item`.
+    Lagged { skipped: u64 },
+}
+
+/// Structured errors from `BroadcastReader::open_validated`'s defensive
+/// path, for a consumer mapping a file it doesn't fully trust to come from
+/// a well-behaved `BroadcastWriter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingError {
+    /// `RingHeader::validate` rejected the header (bad magic, version, or
+    /// element size), or `capacity * size_of::<SeqlockSlot<T>>() +
+    /// size_of::<RingHeader>()` exceeds the mapped region's actual length.
+    /// `open_validated` also returns it when the file can't be opened or the
+    /// consumer registry has no free slot.
+    MalformedHeader,
+    /// A slot's sequence number was observed odd on every attempt within
+    /// the spin budget — the writer appears stuck or crashed mid-write.
+    SpinTimeout,
+    /// A slot's sequence number changed to a different even value between
+    /// our two loads without ever being observed odd: our copy straddled a
+    /// full write we never saw start, which a bounded spin count can't
+    /// distinguish from garbage memory.
+    TornRead,
+}
+
+impl std::fmt::Display for RingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RingError::MalformedHeader => f.write_str("ring header failed validation"),
+            RingError::SpinTimeout => f.write_str("slot read spun out waiting on a stuck writer"),
+            RingError::TornRead => f.write_str("slot read was torn by a racing write"),
+        }
+    }
+}
+
+impl std::error::Error for RingError {}
+
 /// The reader side of a broadcast ring buffer.
 ///
 /// Opens an existing memory-mapped ring buffer file in read-only mode.
@@ -74,6 +177,14 @@ pub struct BroadcastReader<T: Copy> {
     capacity: u64,
     /// Count of overrun events (when reader fell too far behind the writer).
     overruns: u64,
+    /// This reader's index into the header's consumer registry, claimed on
+    /// `open` and released on `Drop`.
+    reader_slot: usize,
+    /// Spin budget for `try_read_validated`, set by `open_validated`. `None`
+    /// for a reader opened via the trusting `open()`, in which case
+    /// `try_read_validated` falls back to an effectively unbounded budget
+    /// (matching `try_read`'s own trusting behavior).
+    max_spins: Option<u32>,
     /// Marker to tie the struct to type `T`.
     _pd: PhantomData<T>,
 }
@@ -109,6 +220,7 @@ impl<T: Copy> BroadcastWriter<T> {
                     RING_VERSION,
                     cfg.capacity as u64,
                     size_of::<T>() as u64,
+                    cfg.bounded,
                 ),
             );
 
@@ -124,6 +236,10 @@ impl<T: Copy> BroadcastWriter<T> {
             base,
             slots_base,
             mask: cfg.mask(),
+            bounded: cfg.bounded,
+            rate_limit: cfg.rate_limit,
+            tokens: cfg.rate_limit.map_or(0.0, |r| r.burst),
+            last_refill_ns: now_ns(),
             _pd: PhantomData,
         })
     }
@@ -132,22 +248,42 @@ impl<T: Copy> BroadcastWriter<T> {
     ///
     /// Multiple producers can open the same file (one `BroadcastWriter` per thread or
     /// process); each `publish()` atomically claims a unique slot via `write_seq`.
+    ///
+    /// Pacing is not part of the on-disk header (it's process-local policy),
+    /// so a writer opened this way starts unpaced; call `set_rate_limit` to
+    /// opt in after the fact.
     pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
         let mut mm = MmapFileMut::open_rw(path)?; // need open_rw in lithos_mmap
         let base = mm.as_mut_ptr();
         let slots_base = unsafe { base.add(size_of::<RingHeader>()) as *mut SeqlockSlot<T> };
         let h = unsafe { &*(base as *const RingHeader) };
         let _ = h.validate::<T>();
-        let cap = h.capacity;
+        let cap = h.capacity();
+        let bounded = h.is_bounded();
         Ok(Self {
             _mm: mm,
             base,
             slots_base,
             mask: cap - 1,
+            bounded,
+            rate_limit: None,
+            tokens: 0.0,
+            last_refill_ns: now_ns(),
             _pd: PhantomData,
         })
     }
 
+    /// Sets (or clears, with `None`) the token-bucket rate limit used by
+    /// `publish_paced`/`try_publish_paced`, refilling the bucket to `burst`.
+    ///
+    /// Useful for a writer opened via `open()`, which has no `RingConfig` to
+    /// read a rate limit from at construction time.
+    pub fn set_rate_limit(&mut self, rate_limit: Option<RateLimit>) {
+        self.rate_limit = rate_limit;
+        self.tokens = rate_limit.map_or(0.0, |r| r.burst);
+        self.last_refill_ns = now_ns();
+    }
+
     /// Returns a reference to the ring header.
     ///
     /// # Safety
@@ -173,6 +309,7 @@ impl<T: Copy> BroadcastWriter<T> {
     /// This is a lock-free operation that:
     /// 1. Atomically increments the write sequence number (claiming a unique slot)
     /// 2. Writes the value to the corresponding slot using the seqlock protocol
+    /// 3. Wakes any reader parked in `read_blocking`, if there is one
     ///
     /// # Concurrency
     /// Do not call from multiple threads using the same `BroadcastWriter` (this type
@@ -182,9 +319,164 @@ impl<T: Copy> BroadcastWriter<T> {
     pub fn publish(&mut self, value: T) {
         // Relaxed ordering is sufficient: the seqlock in the slot provides
         // the necessary synchronization for readers
-        let seq = self.header().write_seq.fetch_add(1, Ordering::Relaxed);
+        let seq = self.header().write_seq().fetch_add(1, Ordering::Relaxed);
         let idx = seq_to_index(seq, self.mask);
         self.slot_mut(idx).write(value);
+
+        // Skip the wake syscall entirely on the common path where no reader
+        // is parked; this keeps publish() as cheap as before chunk1-4 for
+        // callers that only ever poll with try_read().
+        if self.header().has_waiters() {
+            let word = self.header().futex_word_ptr();
+            unsafe { futex::futex_wake(word, i32::MAX) };
+        }
+    }
+
+    /// Returns lag and heartbeat staleness for every currently-registered
+    /// reader, so a supervisor can detect dead or lagging consumers.
+    ///
+    /// This walks the full consumer registry (`shm_layout::MAX_READERS`
+    /// slots), so prefer calling it on a supervisory cadence rather than the
+    /// publish hot path.
+    pub fn reader_lag(&self) -> Vec<ReaderLag> {
+        self.header().reader_lags()
+    }
+
+    /// Classifies every registered reader as live or stale against
+    /// `timeout`, and reports the minimum `read_seq` across the live ones.
+    ///
+    /// Unlike `reader_lag`, a reader whose heartbeat has gone silent for
+    /// longer than `timeout` doesn't count toward `min_live_read_seq` — so a
+    /// dead consumer can't hold back bounded-publish back-pressure forever.
+    /// Pair with `reclaim_stale_readers` to actually free its registry slot.
+    pub fn scan_consumers(&self, timeout: Duration) -> ConsumerScan {
+        self.header().scan_consumers(timeout.as_nanos() as u64)
+    }
+
+    /// Frees every reader slot whose heartbeat is older than `timeout`,
+    /// returning the number reclaimed. Safe to call on a supervisory
+    /// cadence: a reader that's merely slow (but still heartbeating) is
+    /// left alone; only slots that look crashed are reclaimed.
+    pub fn reclaim_stale_readers(&self, timeout: Duration) -> usize {
+        self.header().reclaim_stale(timeout.as_nanos() as u64)
+    }
+
+    /// Publishes a single item, refusing to lap a registered reader.
+    ///
+    /// Only enforces back-pressure when the ring was created with
+    /// `RingConfig::bounded()`; otherwise this behaves exactly like
+    /// `publish()` and always succeeds. When bounded, this compares the
+    /// claimed sequence against a cached minimum reader position and only
+    /// falls back to a full registry scan (`shm_layout::MAX_READERS` slots)
+    /// when the cache suggests the ring is near-full, so the common case
+    /// stays as cheap as `publish()`.
+    ///
+    /// # Errors
+    /// Returns `Backpressure` (without claiming a slot or incrementing
+    /// `write_seq`) if publishing would overwrite a slot a registered reader
+    /// hasn't consumed yet.
+    #[inline(always)]
+    pub fn try_publish(&mut self, value: T) -> Result<(), Backpressure> {
+        if !self.bounded {
+            self.publish(value);
+            return Ok(());
+        }
+
+        let capacity = self.mask + 1;
+        let seq = self.header().write_seq().load(Ordering::Relaxed);
+
+        let mut min_read_seq = self.header().cached_min_read_seq();
+        if seq.saturating_sub(min_read_seq) >= capacity {
+            // Cache looks stale: rescan for a fresh minimum before giving up.
+            min_read_seq = self.header().min_reader_seq();
+            self.header().set_cached_min_read_seq(min_read_seq);
+            if seq.saturating_sub(min_read_seq) >= capacity {
+                return Err(Backpressure);
+            }
+        }
+
+        let claimed = self.header().write_seq().fetch_add(1, Ordering::Relaxed);
+        let idx = seq_to_index(claimed, self.mask);
+        self.slot_mut(idx).write(value);
+
+        if self.header().has_waiters() {
+            let word = self.header().futex_word_ptr();
+            unsafe { futex::futex_wake(word, i32::MAX) };
+        }
+
+        Ok(())
+    }
+
+    /// Allocates the next value from the shared, cross-process
+    /// correlation-ID counter.
+    ///
+    /// Producers can stamp each published record with the returned ID (e.g.
+    /// alongside a `TopOfBook`) so a downstream consumer — potentially in a
+    /// different process — can correlate it back to producer-side timing or
+    /// dedup against it. IDs are unique and increasing per ring but carry no
+    /// relationship to `write_seq`; this is a separate counter.
+    #[inline(always)]
+    pub fn next_correlation_id(&self) -> u64 {
+        self.header().next_correlation_id()
+    }
+
+    /// Refills the token bucket based on elapsed time since the last refill,
+    /// capped at `burst`, and returns the current token count.
+    ///
+    /// No-op (returns 0.0) when `rate_limit` is `None`.
+    fn refill_tokens(&mut self) -> f64 {
+        let Some(limit) = self.rate_limit else {
+            return 0.0;
+        };
+        let now = now_ns();
+        let elapsed_ns = now.saturating_sub(self.last_refill_ns);
+        self.last_refill_ns = now;
+        self.tokens = (self.tokens + elapsed_ns as f64 * limit.rate / 1e9).min(limit.burst);
+        self.tokens
+    }
+
+    /// Publishes a single item, blocking (via a short sleep) until the token
+    /// bucket has accrued enough capacity if the configured rate would
+    /// otherwise be exceeded.
+    ///
+    /// Behaves exactly like `publish()` when no rate limit is configured.
+    pub fn publish_paced(&mut self, value: T) {
+        let Some(limit) = self.rate_limit else {
+            self.publish(value);
+            return;
+        };
+
+        loop {
+            if self.refill_tokens() >= 1.0 {
+                break;
+            }
+            let deficit = 1.0 - self.tokens;
+            let wait_secs = deficit / limit.rate;
+            std::thread::sleep(Duration::from_secs_f64(wait_secs.max(0.0)));
+        }
+        self.tokens -= 1.0;
+        self.publish(value);
+    }
+
+    /// Publishes a single item if the token bucket has capacity, without
+    /// blocking.
+    ///
+    /// Behaves exactly like `publish()` (and always succeeds) when no rate
+    /// limit is configured.
+    ///
+    /// # Errors
+    /// Returns `RateLimited` (without publishing) if the bucket is empty.
+    pub fn try_publish_paced(&mut self, value: T) -> Result<(), RateLimited> {
+        if self.rate_limit.is_none() {
+            self.publish(value);
+            return Ok(());
+        }
+        if self.refill_tokens() < 1.0 {
+            return Err(RateLimited);
+        }
+        self.tokens -= 1.0;
+        self.publish(value);
+        Ok(())
     }
 }
 
@@ -205,6 +497,8 @@ impl<T: Copy> BroadcastReader<T> {
     /// - Invalid magic number (not a ring buffer file)
     /// - Version mismatch
     /// - Element size mismatch (wrong type `T`)
+    /// - The consumer registry is full (`shm_layout::MAX_READERS` readers
+    ///   already registered)
     pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
         let mm = MmapFile::open_ro(path)?;
         let base = mm.as_ptr();
@@ -219,12 +513,17 @@ impl<T: Copy> BroadcastReader<T> {
         h.validate::<T>()
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
-        let cap = h.capacity as u64;
+        let cap = h.capacity() as u64;
         let mask = cap - 1;
 
         // Tail-follow: start reading from the current write position.
         // Acquire ordering ensures we see all writes that happened before this load.
-        let read_seq = h.write_seq.load(Ordering::Acquire);
+        let read_seq = h.write_seq().load(Ordering::Acquire);
+
+        let reader_slot = h.claim_reader_slot().ok_or_else(|| {
+            io::Error::other("reader registry full: too many concurrent readers")
+        })?;
+        h.publish_read_seq(reader_slot, read_seq);
 
         Ok(Self {
             _mm: mm,
@@ -234,6 +533,56 @@ impl<T: Copy> BroadcastReader<T> {
             mask,
             capacity: cap,
             overruns: 0,
+            reader_slot,
+            max_spins: None,
+            _pd: PhantomData,
+        })
+    }
+
+    /// Opens an existing ring buffer for reading via the defensive,
+    /// non-trusting path.
+    ///
+    /// Like `open`, this rejects a header that fails `validate::<T>()`
+    /// outright (here as `RingError::MalformedHeader`). It additionally
+    /// bounds-checks that `capacity * size_of::<SeqlockSlot<T>>() +
+    /// size_of::<RingHeader>()` fits within the mapped file's actual
+    /// length, so a truncated or lied-about-capacity file can't make later
+    /// slot reads walk off the end of the mapping. Each `try_read_validated`
+    /// call then gives up on a slot after `max_spins` failed attempts
+    /// rather than spinning forever on a stuck or crashed writer.
+    ///
+    /// Use this instead of `open` when the file wasn't necessarily produced
+    /// by a `BroadcastWriter` in this codebase that you trust.
+    pub fn open_validated<P: AsRef<Path>>(path: P, max_spins: u32) -> Result<Self, RingError> {
+        let mm = MmapFile::open_ro(path).map_err(|_| RingError::MalformedHeader)?;
+        let base = mm.as_ptr();
+        let region_len = mm.len();
+
+        let h = unsafe { &*(base as *const RingHeader) };
+        h.validate::<T>().map_err(|_| RingError::MalformedHeader)?;
+
+        let cap = h.capacity() as u64;
+        let required = size_of::<RingHeader>() + cap as usize * size_of::<SeqlockSlot<T>>();
+        if required > region_len {
+            return Err(RingError::MalformedHeader);
+        }
+
+        let slots_base = unsafe { base.add(size_of::<RingHeader>()) as *const SeqlockSlot<T> };
+        let mask = cap - 1;
+        let read_seq = h.write_seq().load(Ordering::Acquire);
+        let reader_slot = h.claim_reader_slot().ok_or(RingError::MalformedHeader)?;
+        h.publish_read_seq(reader_slot, read_seq);
+
+        Ok(Self {
+            _mm: mm,
+            base,
+            slots_base,
+            read_seq,
+            mask,
+            capacity: cap,
+            overruns: 0,
+            reader_slot,
+            max_spins: Some(max_spins),
             _pd: PhantomData,
         })
     }
@@ -281,7 +630,7 @@ impl<T: Copy> BroadcastReader<T> {
     #[inline(always)]
     pub fn try_read(&mut self) -> Option<T> {
         // Acquire ordering ensures we see the most recent write_seq
-        let w = self.header().write_seq.load(Ordering::Acquire);
+        let w = self.header().write_seq().load(Ordering::Acquire);
 
         // No new data available
         if self.read_seq >= w {
@@ -297,9 +646,114 @@ impl<T: Copy> BroadcastReader<T> {
         let idx = seq_to_index(self.read_seq, self.mask);
         let v = self.slot(idx).read();
         self.read_seq += 1;
+
+        // Publish our advancing position into the registry so the writer's
+        // reader_lag() scan sees it without needing to ask us directly.
+        self.header().publish_read_seq(self.reader_slot, self.read_seq);
+
         Some(v)
     }
 
+    /// Like `try_read`, but reports a lap as a distinct `ReadOutcome::Lagged`
+    /// instead of silently fast-forwarding and handing back whatever item
+    /// the reader landed on.
+    ///
+    /// A caller that needs to know when data was skipped (rather than just
+    /// tracking the cumulative `dropped_count`) should poll with this
+    /// instead of `try_read`.
+    #[inline(always)]
+    pub fn try_read_outcome(&mut self) -> ReadOutcome<T> {
+        let w = self.header().write_seq().load(Ordering::Acquire);
+
+        if self.read_seq >= w {
+            return ReadOutcome::Empty;
+        }
+
+        if w - self.read_seq > self.capacity {
+            let behind_before = w - self.read_seq;
+            apply_overrun_policy(w, &mut self.read_seq, self.capacity, &mut self.overruns);
+            return ReadOutcome::Lagged {
+                skipped: behind_before - self.capacity,
+            };
+        }
+
+        let idx = seq_to_index(self.read_seq, self.mask);
+        let v = self.slot(idx).read();
+        self.read_seq += 1;
+        self.header().publish_read_seq(self.reader_slot, self.read_seq);
+
+        ReadOutcome::Item(v)
+    }
+
+    /// Cumulative count of items lost to overruns since this reader was
+    /// opened. Alias for `overruns()` using the vocabulary of
+    /// `try_read_outcome`'s `Lagged` variant.
+    pub fn dropped_count(&self) -> u64 {
+        self.overruns
+    }
+
+    /// Defensive counterpart to `try_read`: bounds the number of spins
+    /// spent waiting on a slot's seqlock to stabilize, and reports *why* it
+    /// gave up instead of ever reading undefined memory.
+    ///
+    /// Returns `Ok(None)` when no new item is available (same as `try_read`
+    /// returning `None`). Overrun recovery still applies silently, exactly
+    /// as in `try_read`; this method only changes what happens when the
+    /// slot itself looks wrong.
+    #[inline(always)]
+    pub fn try_read_validated(&mut self) -> Result<Option<T>, RingError> {
+        let w = self.header().write_seq().load(Ordering::Acquire);
+        if self.read_seq >= w {
+            return Ok(None);
+        }
+
+        if w - self.read_seq > self.capacity {
+            apply_overrun_policy(w, &mut self.read_seq, self.capacity, &mut self.overruns);
+        }
+
+        let idx = seq_to_index(self.read_seq, self.mask);
+        let spins = self.max_spins.unwrap_or(u32::MAX);
+        let v = match self.slot(idx).try_read_bounded(spins) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Err(RingError::SpinTimeout),
+            Err(()) => return Err(RingError::TornRead),
+        };
+        self.read_seq += 1;
+        self.header().publish_read_seq(self.reader_slot, self.read_seq);
+
+        Ok(Some(v))
+    }
+
+    /// Stamps this reader's registry slot with the current time.
+    ///
+    /// `try_read` advances `read_seq` on every call but does not itself imply
+    /// liveness for a reader that's caught up and calling `try_read`
+    /// repeatedly without progress; call this on whatever cadence your
+    /// supervisor expects (e.g. once per poll loop iteration) so
+    /// `BroadcastWriter::reader_lag()` can distinguish a caught-up reader
+    /// from a dead one.
+    pub fn heartbeat(&self) {
+        self.header().heartbeat(self.reader_slot);
+    }
+
+    /// Reads the shared correlation-ID counter's current value.
+    ///
+    /// Read-only: readers correlate against IDs a writer already allocated
+    /// via `BroadcastWriter::next_correlation_id`, they don't allocate their
+    /// own.
+    pub fn current_correlation_id(&self) -> u64 {
+        self.header().peek_correlation_id()
+    }
+
+    /// Alias for `current_correlation_id`, named for the checkpoint/resume
+    /// use case: a consumer persists this value before shutting down, then
+    /// compares it against `next_correlation_id` on the next startup to know
+    /// how many IDs (and, assuming one-correlation-ID-per-publish producer
+    /// discipline, events) were allocated while it was down.
+    pub fn last_correlation_id(&self) -> u64 {
+        self.current_correlation_id()
+    }
+
     /// Returns the total count of overrun events since this reader was opened.
     ///
     /// An overrun occurs when the writer laps the reader, meaning some messages
@@ -307,4 +761,186 @@ impl<T: Copy> BroadcastReader<T> {
     pub fn overruns(&self) -> u64 {
         self.overruns
     }
+
+    /// Number of `try_read` attempts to spin through before parking via
+    /// futex in `read_blocking`. A writer that's mid-publish when we first
+    /// check often delivers within a few spins, so this avoids paying a
+    /// `register_waiter`/syscall round-trip for waits that would have
+    /// resolved almost immediately anyway.
+    const BLOCKING_SPIN_ATTEMPTS: u32 = 64;
+
+    /// Blocks until an item is available or `timeout` elapses, parking the
+    /// thread instead of busy-polling.
+    ///
+    /// Replaces the common `try_read()` + `sleep(1ms)` poll loop: on Linux
+    /// this parks via `FUTEX_WAIT` on the header's `write_seq` word and is
+    /// woken directly by the writer's `publish()`/`try_publish()`, so there's
+    /// no fixed polling latency and no idle CPU while waiting. `timeout` of
+    /// `None` waits indefinitely; `Some(d)` returns `None` if no item arrives
+    /// within `d`.
+    pub fn read_blocking(&mut self, timeout: Option<Duration>) -> Option<T> {
+        let deadline = timeout.map(|d| Instant::now() + d);
+
+        loop {
+            for _ in 0..Self::BLOCKING_SPIN_ATTEMPTS {
+                if let Some(v) = self.try_read() {
+                    return Some(v);
+                }
+                std::hint::spin_loop();
+            }
+
+            let remaining = match deadline {
+                Some(dl) => {
+                    let now = Instant::now();
+                    if now >= dl {
+                        return None;
+                    }
+                    Some(dl - now)
+                }
+                None => None,
+            };
+
+            self.header().register_waiter();
+
+            // Re-check for the lost-wakeup window: a publish between our
+            // try_read() miss above and register_waiter() would have found
+            // no parked waiter to notify.
+            let w = self.header().write_seq().load(Ordering::Acquire);
+            if self.read_seq < w {
+                self.header().unregister_waiter();
+                continue;
+            }
+
+            let word = self.header().futex_word_ptr();
+            // write_seq only ever grows, so its low 32 bits are a reasonable
+            // futex word: see `RingHeader::futex_word_ptr` for why a
+            // truncation collision is harmless here.
+            let expected = w as u32;
+            unsafe { futex::futex_wait(word, expected, remaining) };
+
+            self.header().unregister_waiter();
+        }
+    }
+}
+
+impl<T: Copy> Drop for BroadcastReader<T> {
+    fn drop(&mut self) {
+        self.header().release_reader_slot(self.reader_slot);
+    }
+}
+
+/// A `Send + Sync` writer handle over the same ring layout as
+/// `BroadcastWriter`, letting many threads in one process publish through a
+/// single shared instance (e.g. `Arc<MultiWriter<T>>`) instead of each
+/// opening its own mmap handle.
+///
+/// Producers claim a slot the same way `BroadcastWriter::publish` does: a
+/// single atomic `fetch_add` on the header's `write_seq` hands out a unique
+/// ticket, which maps to `ticket & mask`. Two producers racing `publish`
+/// always get distinct indices unless their tickets are a full `capacity`
+/// apart, so they never interleave writes to the same slot — the same
+/// invariant that already lets independent `BroadcastWriter` instances share
+/// a ring across threads or processes.
+pub struct MultiWriter<T: Copy> {
+    /// Owns the mmap lifetime; kept alive but not directly accessed after init.
+    _mm: MmapFileMut,
+    /// Cached pointer to the first slot in the ring.
+    slots_base: *mut SeqlockSlot<T>,
+    /// Raw pointer to the start of the mapped region (header location).
+    base: *mut u8,
+    /// Bitmask for fast modulo: `index = ticket & mask`.
+    mask: u64,
+    /// Marker to tie the struct to type `T` without storing a `T`.
+    _pd: PhantomData<T>,
+}
+
+// SAFETY: every mutation through a shared `&MultiWriter` goes through either
+// an atomic (`write_seq.fetch_add`) or the slot's own seqlock protocol
+// (`SeqlockSlot::write`'s odd/even `Release` stores), both of which are
+// race-free under concurrent calls from multiple threads. `_mm` is never
+// touched again after `create`/`open` returns.
+unsafe impl<T: Copy> Send for MultiWriter<T> {}
+unsafe impl<T: Copy> Sync for MultiWriter<T> {}
+
+impl<T: Copy> MultiWriter<T> {
+    /// Creates a new broadcast ring buffer at the given file path, returning
+    /// a handle producer threads can share directly.
+    pub fn create<P: AsRef<Path>>(path: P, cfg: RingConfig) -> io::Result<Self> {
+        let bytes = bytes_for_ring::<T>(cfg.capacity);
+        let mut mm = MmapFileMut::create_rw(path, bytes)?;
+        let base = mm.as_mut_ptr();
+        let slots_base = unsafe { base.add(size_of::<RingHeader>()) as *mut SeqlockSlot<T> };
+
+        // SAFETY: we just created this mmap region exclusively, so we have
+        // sole access, and it's sized correctly for the header + slots.
+        unsafe {
+            let h = base as *mut RingHeader;
+            ptr::write(
+                h,
+                RingHeader::new(RING_MAGIC, RING_VERSION, cfg.capacity as u64, size_of::<T>() as u64, cfg.bounded),
+            );
+            for i in 0..cfg.capacity {
+                let s = &mut *slots_base.add(i);
+                s.init();
+            }
+        }
+
+        Ok(Self {
+            _mm: mm,
+            base,
+            slots_base,
+            mask: cfg.mask(),
+            _pd: PhantomData,
+        })
+    }
+
+    /// Opens an existing ring buffer, returning a shared handle any number
+    /// of producer threads can publish through concurrently.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut mm = MmapFileMut::open_rw(path)?;
+        let base = mm.as_mut_ptr();
+        let slots_base = unsafe { base.add(size_of::<RingHeader>()) as *mut SeqlockSlot<T> };
+        let h = unsafe { &*(base as *const RingHeader) };
+        let _ = h.validate::<T>();
+        let cap = h.capacity();
+        Ok(Self {
+            _mm: mm,
+            base,
+            slots_base,
+            mask: cap - 1,
+            _pd: PhantomData,
+        })
+    }
+
+    #[inline(always)]
+    fn header(&self) -> &RingHeader {
+        // SAFETY: base points to a valid RingHeader that we initialized or validated
+        unsafe { &*(self.base as *const RingHeader) }
+    }
+
+    /// Publishes a single item, claiming a slot atomically so any number of
+    /// threads can call this concurrently through a shared `&MultiWriter`.
+    #[inline(always)]
+    pub fn publish(&self, value: T) {
+        let ticket = self.header().write_seq().fetch_add(1, Ordering::Relaxed);
+        let idx = seq_to_index(ticket, self.mask);
+
+        // SAFETY: `idx` is masked into `[0, capacity)`, and the ticket
+        // scheme above guarantees no other thread holds the same index at
+        // the same time (barring a full-capacity lap, the same overwrite
+        // semantics every publisher in this module already accepts).
+        unsafe { (*self.slots_base.add(idx as usize)).write(value) };
+
+        if self.header().has_waiters() {
+            let word = self.header().futex_word_ptr();
+            unsafe { futex::futex_wake(word, i32::MAX) };
+        }
+    }
+
+    /// Allocates the next value from the shared, cross-process
+    /// correlation-ID counter (see `BroadcastWriter::next_correlation_id`).
+    #[inline(always)]
+    pub fn next_correlation_id(&self) -> u64 {
+        self.header().next_correlation_id()
+    }
 }
@@ -0,0 +1,292 @@
+//! Variable-length framed byte ring: a sibling to [`crate::broadcast`] for
+//! payloads that don't fit a fixed-size `T`.
+//!
+//! `BroadcastWriter<T>`/`BroadcastReader<T>` require every slot to be the same
+//! `size_of::<T>()`, which is fine for POD market-data structs but can't carry
+//! e.g. variable-length exchange messages or serialized control frames. This
+//! module stores raw bytes instead: the writer claims a byte range with
+//! `fetch_add` on a cursor (rather than a slot index), frames each record with
+//! a small header, and wraps/pads at the end of the buffer the way Aeron-style
+//! ring buffers do.
+//!
+//! # Record framing
+//!
+//! ```text
+//! ┌──────────┬──────────┬───────────────────────┬─────────┐
+//! │ len: u32 │ tag: u32 │ payload (len bytes)    │ pad     │
+//! └──────────┴──────────┴───────────────────────┴─────────┘
+//!   8-byte header          payload                 pad out to 8-byte boundary
+//! ```
+//!
+//! A `len` of `0` marks a padding frame used to fill the tail of the buffer
+//! when a real record wouldn't otherwise fit before wrapping; readers skip it
+//! and resume at offset 0.
+
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use lithos_mmap::{MmapFile, MmapFileMut};
+
+/// Magic number identifying a valid byte-ring file ("LITHOSBY").
+const BYTE_RING_MAGIC: u64 = 0x4C49_5448_4F53_4259;
+const BYTE_RING_VERSION: u64 = 1;
+
+/// Size in bytes of a record's framing header (`len` + `tag`).
+const RECORD_HEADER_LEN: usize = 8;
+
+/// Header at the start of a byte-ring's memory-mapped region.
+///
+/// # Representation
+/// Uses `#[repr(C)]`; fits in one cache line (64 bytes) so it never
+/// false-shares with the first bytes of the data region.
+#[repr(C)]
+struct ByteRingHeader {
+    magic: u64,
+    version: u64,
+    /// Total size of the data region in bytes. Must be a power of 2 so the
+    /// writer's byte cursor can wrap with a mask instead of a division.
+    capacity: u64,
+    /// Byte offset of the next record to be claimed by a writer.
+    write_cursor: AtomicU64,
+    _pad: [u8; 32],
+}
+
+impl ByteRingHeader {
+    fn new(capacity: u64) -> Self {
+        Self {
+            magic: BYTE_RING_MAGIC,
+            version: BYTE_RING_VERSION,
+            capacity,
+            write_cursor: AtomicU64::new(0),
+            _pad: [0; 32],
+        }
+    }
+
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.magic != BYTE_RING_MAGIC {
+            return Err("Bad magic");
+        }
+        if self.version != BYTE_RING_VERSION {
+            return Err("Wrong version");
+        }
+        if !(self.capacity as usize).is_power_of_two() {
+            return Err("Capacity must be power of two");
+        }
+        Ok(())
+    }
+}
+
+/// Rounds `n` up to the next multiple of 8.
+#[inline(always)]
+fn align8(n: usize) -> usize {
+    (n + 7) & !7
+}
+
+/// The writer side of a variable-length framed byte ring.
+pub struct ByteBroadcastWriter {
+    _mm: MmapFileMut,
+    base: *mut u8,
+    data_base: *mut u8,
+    capacity: u64,
+    mask: u64,
+}
+
+/// The reader side of a variable-length framed byte ring.
+pub struct ByteBroadcastReader {
+    _mm: MmapFile,
+    base: *const u8,
+    data_base: *const u8,
+    capacity: u64,
+    mask: u64,
+    /// Local read cursor: byte offset of the next record to consume.
+    read_cursor: u64,
+}
+
+impl ByteBroadcastWriter {
+    /// Creates a new byte ring at `path` with a data region of `capacity`
+    /// bytes (must be a power of 2).
+    ///
+    /// # Panics
+    /// Panics if `capacity` is not a power of 2. `capacity` should also be
+    /// large relative to the records it carries: a record never straddles
+    /// the end of the buffer, so a record wider than the remaining tail
+    /// forces a padding frame for the rest of it.
+    pub fn create<P: AsRef<Path>>(path: P, capacity: usize) -> io::Result<Self> {
+        assert!(capacity.is_power_of_two(), "capacity must be power of 2");
+        let bytes = std::mem::size_of::<ByteRingHeader>() + capacity;
+        let mut mm = MmapFileMut::create_rw(path, bytes as u64)?;
+        let base = mm.as_mut_ptr();
+        let data_base = unsafe { base.add(std::mem::size_of::<ByteRingHeader>()) };
+
+        unsafe {
+            let h = base as *mut ByteRingHeader;
+            std::ptr::write(h, ByteRingHeader::new(capacity as u64));
+        }
+
+        Ok(Self {
+            _mm: mm,
+            base,
+            data_base,
+            capacity: capacity as u64,
+            mask: capacity as u64 - 1,
+        })
+    }
+
+    #[inline(always)]
+    fn header(&self) -> &ByteRingHeader {
+        unsafe { &*(self.base as *const ByteRingHeader) }
+    }
+
+    /// Writes `len` bytes of `frame` (header + payload + padding) at data
+    /// offset `idx` as one contiguous copy; it does not wrap at the end of
+    /// the data region.
+    ///
+    /// # Safety
+    /// `idx + frame.len()` must not exceed `self.capacity` as measured from
+    /// `idx` with wraparound, i.e. the caller must have already confirmed the
+    /// frame fits without straddling the end (or is itself the padding frame
+    /// that fills the remainder).
+    unsafe fn write_at(&mut self, idx: u64, frame: &[u8]) {
+        unsafe {
+            let dst = self.data_base.add(idx as usize);
+            std::ptr::copy_nonoverlapping(frame.as_ptr(), dst, frame.len());
+        }
+    }
+
+    /// Publishes `payload` tagged with `tag`, returning the aligned frame
+    /// length claimed on the ring.
+    ///
+    /// The writer claims space for the record with a single `fetch_add` on
+    /// the byte cursor; when the record (or a padding frame ahead of it)
+    /// would straddle the end of the data region, a zero-length padding frame
+    /// fills the tail and the real record wraps to offset 0.
+    pub fn publish(&mut self, tag: u32, payload: &[u8]) -> u64 {
+        let record_len = align8(RECORD_HEADER_LEN + payload.len()) as u64;
+        let remaining_to_end = |idx: u64| self.capacity - (idx & self.mask);
+
+        loop {
+            let idx = self
+                .header()
+                .write_cursor
+                .fetch_add(record_len, Ordering::Relaxed)
+                & self.mask;
+
+            let room = remaining_to_end(idx);
+            if room < record_len {
+                // Not enough room before the end: this claim becomes a
+                // padding frame filling the tail; the actual record is
+                // claimed on the next loop iteration, which starts at 0.
+                //
+                // Only `room` bytes are actually written as padding, so only
+                // `room` bytes must end up claimed here — the `fetch_add`
+                // above already staked out `record_len`, so give back the
+                // `record_len - room` difference. Otherwise the cursor would
+                // land at offset `record_len - room` into the new lap instead
+                // of exactly 0, desyncing it from `try_read`'s padding-skip,
+                // which always resumes at the start of the new lap.
+                let mut pad = vec![0u8; room as usize];
+                pad[0..4].copy_from_slice(&0u32.to_le_bytes()); // len = 0 marks padding
+                pad[4..8].copy_from_slice(&0u32.to_le_bytes());
+                unsafe { self.write_at(idx, &pad) };
+                self.header().write_cursor.fetch_sub(record_len - room, Ordering::Relaxed);
+                continue;
+            }
+
+            let mut frame = Vec::with_capacity(record_len as usize);
+            frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            frame.extend_from_slice(&tag.to_le_bytes());
+            frame.extend_from_slice(payload);
+            frame.resize(record_len as usize, 0);
+
+            unsafe { self.write_at(idx, &frame) };
+            return record_len;
+        }
+    }
+}
+
+impl ByteBroadcastReader {
+    /// Opens an existing byte ring for reading, starting at the current
+    /// write position (tail-follow mode).
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mm = MmapFile::open_ro(path)?;
+        let base = mm.as_ptr();
+        let data_base = unsafe { base.add(std::mem::size_of::<ByteRingHeader>()) };
+        let h = unsafe { &*(base as *const ByteRingHeader) };
+        h.validate()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let capacity = h.capacity;
+        let read_cursor = h.write_cursor.load(Ordering::Acquire);
+
+        Ok(Self {
+            _mm: mm,
+            base,
+            data_base,
+            capacity,
+            mask: capacity - 1,
+            read_cursor,
+        })
+    }
+
+    #[inline(always)]
+    fn header(&self) -> &ByteRingHeader {
+        unsafe { &*(self.base as *const ByteRingHeader) }
+    }
+
+    /// Reads the 8-byte record header at data offset `idx`: `(len, tag)`.
+    fn read_header(&self, idx: u64) -> (u32, u32) {
+        unsafe {
+            let p = self.data_base.add(idx as usize);
+            let mut len_bytes = [0u8; 4];
+            let mut tag_bytes = [0u8; 4];
+            std::ptr::copy_nonoverlapping(p, len_bytes.as_mut_ptr(), 4);
+            std::ptr::copy_nonoverlapping(p.add(4), tag_bytes.as_mut_ptr(), 4);
+            (u32::from_le_bytes(len_bytes), u32::from_le_bytes(tag_bytes))
+        }
+    }
+
+    /// Attempts to read the next record.
+    ///
+    /// Skips padding frames transparently. Returns `(tag, payload)` or `None`
+    /// if the reader has caught up to the writer.
+    pub fn try_read(&mut self) -> Option<(u32, Vec<u8>)> {
+        loop {
+            let w = self.header().write_cursor.load(Ordering::Acquire);
+            if self.read_cursor >= w {
+                return None;
+            }
+
+            let idx = self.read_cursor & self.mask;
+            let (len, tag) = self.read_header(idx);
+            let record_len = align8(RECORD_HEADER_LEN + len as usize) as u64;
+
+            if len == 0 {
+                // Padding frame: skip to the start of the next wrap.
+                let room = self.capacity - idx;
+                self.read_cursor += room;
+                continue;
+            }
+
+            // A corrupted or desynced cursor can hand back a bogus `len`
+            // read from stale/garbage bytes; a record can never legitimately
+            // straddle the end of the data region (that forces a wraparound
+            // instead), so reject that — which also catches `record_len`
+            // wider than the whole region — as "caught up" rather than
+            // driving a multi-GB allocation or reading past the mapped
+            // region.
+            if idx + record_len > self.capacity {
+                return None;
+            }
+
+            let mut payload = vec![0u8; len as usize];
+            unsafe {
+                let p = self.data_base.add(idx as usize + RECORD_HEADER_LEN);
+                std::ptr::copy_nonoverlapping(p, payload.as_mut_ptr(), len as usize);
+            }
+
+            self.read_cursor += record_len;
+            return Some((tag, payload));
+        }
+    }
+}
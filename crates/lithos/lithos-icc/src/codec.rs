@@ -0,0 +1,28 @@
+//! Wire codec trait for items transported over a broadcast bus.
+//!
+//! `BroadcastWriter<T>`/`BroadcastReader<T>` move `T` by raw in-memory copy
+//! within a single mmap region, but persisting a live session to disk (see
+//! [`crate::journal`]) or replaying it later needs a stable, versioned byte
+//! encoding that survives across process runs and doesn't depend on struct
+//! padding/alignment. `BusCodec` is that encoding.
+
+use std::io::{self, Write};
+
+/// Encodes/decodes a bus item to/from a stable little-endian wire format.
+///
+/// Implementors should keep the wire layout in lockstep with their in-memory
+/// shape, field for field, so `encode`/`decode` round-trip exactly. `decode`
+/// reads from the front of `buf` and reports how many bytes it consumed, so
+/// a journal file can hold many records back to back without its own framing.
+pub trait BusCodec: Sized {
+    /// Number of bytes `encode` always writes and `decode` always consumes.
+    const ENCODED_LEN: usize;
+
+    /// Serializes `self` into `out`.
+    fn encode(&self, out: &mut impl Write) -> io::Result<()>;
+
+    /// Parses a value from the front of `buf`.
+    ///
+    /// Returns `None` if `buf` is shorter than `Self::ENCODED_LEN`.
+    fn decode(buf: &[u8]) -> Option<(Self, usize)>;
+}
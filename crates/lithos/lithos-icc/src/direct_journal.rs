@@ -0,0 +1,219 @@
+//! O_DIRECT, page-aligned append-only journal for crash-durable capture.
+//!
+//! [`Journal`](crate::journal::Journal) buffers through the page cache via a
+//! plain `BufWriter`, which is fine for replay/backtesting but leaves a
+//! window where a crash loses writes the OS hadn't flushed yet. `DirectJournal`
+//! instead packs records into 4096-byte pages and writes whole pages through
+//! `O_DIRECT` on Linux, bypassing the page cache entirely. Each page carries
+//! a CRC over its own content, so [`DirectJournalReplayer`] can verify every
+//! page it reads and stop cleanly at the last fully-written one instead of
+//! parsing corrupt trailing bytes left by a crash mid-write.
+//!
+//! # Page layout
+//! ```text
+//! ┌──────────┬──────────────────────────────────────────────────────┐
+//! │ crc: u32 │ records: [seq: u64][len: u32][payload] ... zero pad  │
+//! └──────────┴──────────────────────────────────────────────────────┘
+//!                       PAGE_SIZE (4096) bytes total
+//! ```
+//! A record with `len == 0` marks the end of records within a page (the
+//! rest of the page is zero padding); `crc` covers every byte after it.
+
+use crate::BroadcastWriter;
+use crate::codec::BusCodec;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+#[cfg(target_os = "linux")]
+use std::os::unix::fs::OpenOptionsExt;
+
+/// Page size used for both the O_DIRECT write buffer and page-level CRCs.
+/// Matches the block size `O_DIRECT` requires alignment to on virtually all
+/// Linux filesystems/block devices.
+pub const PAGE_SIZE: usize = 4096;
+
+/// `seq: u64` + `len: u32` per record.
+const RECORD_HEADER_LEN: usize = 12;
+
+/// CRC-32 (IEEE 802.3 polynomial), computed bit-by-bit rather than via a
+/// lookup table: pages are small (4 KiB) and written only once per flush,
+/// so a table's extra footprint isn't worth it here.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// A `PAGE_SIZE`-aligned buffer, since `O_DIRECT` requires the write buffer's
+/// memory address (not just its length) to be block-aligned on Linux.
+#[repr(C, align(4096))]
+struct AlignedPage([u8; PAGE_SIZE]);
+
+/// Append-only, page-aligned, `O_DIRECT`-backed journal.
+///
+/// Wraps a `BroadcastWriter` so callers publish through the journal instead,
+/// exactly like [`crate::journal::Journal`].
+pub struct DirectJournal<T: Copy + BusCodec> {
+    writer: BroadcastWriter<T>,
+    file: File,
+    page: Box<AlignedPage>,
+    /// Byte offset within `page` where the next record header would start.
+    cursor: usize,
+    next_seq: u64,
+}
+
+impl<T: Copy + BusCodec> DirectJournal<T> {
+    /// Wraps an existing `BroadcastWriter`, creating (truncating) a journal
+    /// file at `path`.
+    ///
+    /// Opens with `O_DIRECT` on Linux; other platforms fall back to ordinary
+    /// buffered writes; `O_DIRECT` is a Linux-specific flag and other OSes
+    /// expose the equivalent intent through different, unwired-up APIs.
+    pub fn create<P: AsRef<Path>>(writer: BroadcastWriter<T>, path: P) -> io::Result<Self> {
+        let mut options = OpenOptions::new();
+        options.create(true).write(true).truncate(true);
+        #[cfg(target_os = "linux")]
+        options.custom_flags(libc::O_DIRECT);
+        let file = options.open(path)?;
+
+        Ok(Self {
+            writer,
+            file,
+            page: Box::new(AlignedPage([0u8; PAGE_SIZE])),
+            cursor: 4, // reserve the leading CRC slot
+            next_seq: 0,
+        })
+    }
+
+    /// Publishes `value` to the live bus and appends it to the journal,
+    /// flushing a full page to disk whenever the current one fills up.
+    ///
+    /// Like `Journal::publish`, the journal write happens first: a crash
+    /// mid-write leaves a torn page (detected and stopped at by
+    /// `DirectJournalReplayer`) rather than an event that reached live
+    /// readers but was never durably captured.
+    pub fn publish(&mut self, value: T) -> io::Result<()> {
+        let mut payload = Vec::with_capacity(T::ENCODED_LEN);
+        value.encode(&mut payload)?;
+
+        // +4 keeps at least 4 spare bytes after every record, so a record
+        // never ends flush with the page. Replay stops at a zero `len` in
+        // the zero padding, or when fewer than `RECORD_HEADER_LEN` bytes remain.
+        let needed = RECORD_HEADER_LEN + payload.len() + 4;
+        if self.cursor + needed > PAGE_SIZE {
+            self.flush_page()?;
+        }
+
+        let page = &mut self.page.0;
+        page[self.cursor..self.cursor + 8].copy_from_slice(&self.next_seq.to_le_bytes());
+        self.cursor += 8;
+        page[self.cursor..self.cursor + 4].copy_from_slice(&(payload.len() as u32).to_le_bytes());
+        self.cursor += 4;
+        page[self.cursor..self.cursor + payload.len()].copy_from_slice(&payload);
+        self.cursor += payload.len();
+        self.next_seq += 1;
+
+        self.writer.publish(value);
+        Ok(())
+    }
+
+    /// Zero-pads the rest of the current page, stamps its CRC, writes the
+    /// whole page, and starts a fresh one.
+    fn flush_page(&mut self) -> io::Result<()> {
+        for b in &mut self.page.0[self.cursor..] {
+            *b = 0;
+        }
+        let crc = crc32(&self.page.0[4..]);
+        self.page.0[0..4].copy_from_slice(&crc.to_le_bytes());
+        self.file.write_all(&self.page.0)?;
+
+        for b in self.page.0.iter_mut() {
+            *b = 0;
+        }
+        self.cursor = 4;
+        Ok(())
+    }
+
+    /// Flushes any buffered records as a final (zero-padded) page and syncs
+    /// the file to disk. Call before dropping to avoid losing a partially
+    /// filled page that hasn't reached `PAGE_SIZE` yet.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.cursor > 4 {
+            self.flush_page()?;
+        }
+        self.file.sync_all()
+    }
+}
+
+/// Reads a [`DirectJournal`] file back, verifying each page's CRC and
+/// stopping at the first page that doesn't validate — a torn tail page left
+/// by a crash mid-write.
+pub struct DirectJournalReplayer {
+    file: File,
+}
+
+impl DirectJournalReplayer {
+    /// Opens an existing journal file for replay.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self {
+            file: File::open(path)?,
+        })
+    }
+
+    /// Replays every valid record in the journal through `writer`, in order.
+    ///
+    /// Stops (without error) at the first short or CRC-mismatched page: both
+    /// indicate a crash mid-write rather than a corrupt-but-complete file.
+    pub fn replay<T: Copy + BusCodec>(&mut self, writer: &mut BroadcastWriter<T>) -> io::Result<()> {
+        let mut page = [0u8; PAGE_SIZE];
+        loop {
+            let n = read_as_much_as_possible(&mut self.file, &mut page)?;
+            if n < PAGE_SIZE {
+                return Ok(());
+            }
+
+            let stored_crc = u32::from_le_bytes(page[0..4].try_into().unwrap());
+            if crc32(&page[4..]) != stored_crc {
+                return Ok(());
+            }
+
+            let mut cursor = 4;
+            while cursor + RECORD_HEADER_LEN <= PAGE_SIZE {
+                let len = u32::from_le_bytes(page[cursor + 8..cursor + 12].try_into().unwrap());
+                if len == 0 {
+                    break;
+                }
+                cursor += RECORD_HEADER_LEN;
+                if cursor + len as usize > PAGE_SIZE {
+                    return Ok(());
+                }
+                let payload = &page[cursor..cursor + len as usize];
+                let Some((value, _)) = T::decode(payload) else {
+                    return Ok(());
+                };
+                writer.publish(value);
+                cursor += len as usize;
+            }
+        }
+    }
+}
+
+/// Reads into `buf` until it's full or the file ends, returning however
+/// many bytes were actually read (short of `buf.len()` at end of file).
+fn read_as_much_as_possible(file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        match file.read(&mut buf[total..])? {
+            0 => break,
+            n => total += n,
+        }
+    }
+    Ok(total)
+}
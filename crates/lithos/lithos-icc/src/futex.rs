@@ -0,0 +1,125 @@
+//! Futex-style wakeups backing `BroadcastReader::read_blocking`.
+//!
+//! Both the Linux and Windows implementations here wait on a plain
+//! shared-memory word rather than a named OS synchronization object: the
+//! word is the low 32 bits of the header's `write_seq`, so a reader can
+//! park on it and any writer process mapping the same file can wake it,
+//! with nothing to name, create, or clean up.
+//!
+//! - Linux: `FUTEX_WAIT`/`FUTEX_WAKE`.
+//! - Windows: `WaitOnAddress`/`WakeByAddressAll`, the documented Win32
+//!   equivalent of a futex (available since Windows 8 / Server 2012),
+//!   letting the same word-based protocol work unchanged instead of
+//!   needing a `CreateEventW` handle shared out-of-band between processes.
+//!
+//! Other targets fall back to a short bounded sleep, which is worse (idle
+//! CPU / latency trade the same as a manual poll loop) but keeps
+//! `read_blocking` usable everywhere the rest of the crate is.
+
+use std::time::Duration;
+
+/// Blocks the calling thread until `*word != expected` or `timeout` elapses.
+///
+/// # Safety
+/// `word` must point to a valid, live `u32` for the duration of the call.
+#[cfg(target_os = "linux")]
+pub(crate) unsafe fn futex_wait(word: *const u32, expected: u32, timeout: Option<Duration>) {
+    let ts = timeout.map(|d| libc::timespec {
+        tv_sec: d.as_secs() as libc::time_t,
+        tv_nsec: d.subsec_nanos() as i64,
+    });
+    let ts_ptr = ts
+        .as_ref()
+        .map_or(std::ptr::null(), |t| t as *const libc::timespec);
+
+    // SAFETY: `word` is valid per the caller's contract; FUTEX_WAIT is a
+    // pure read-and-block on the kernel side and does not write through it.
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            word,
+            libc::FUTEX_WAIT,
+            expected,
+            ts_ptr,
+            std::ptr::null::<u32>(),
+            0,
+        );
+    }
+}
+
+/// Wakes up to `n` threads parked on `word` via `futex_wait`.
+///
+/// # Safety
+/// `word` must point to a valid, live `u32`.
+#[cfg(target_os = "linux")]
+pub(crate) unsafe fn futex_wake(word: *const u32, n: i32) {
+    // SAFETY: `word` is valid per the caller's contract.
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            word,
+            libc::FUTEX_WAKE,
+            n,
+            std::ptr::null::<libc::timespec>(),
+            std::ptr::null::<u32>(),
+            0,
+        );
+    }
+}
+
+/// Raw bindings for the Win32 address-based wait/wake pair. Exported from
+/// `api-ms-win-core-synch-l1-2-0.dll`, forwarded through `kernel32.lib` on
+/// every SDK new enough to have them (Windows 8+).
+#[cfg(windows)]
+#[allow(non_snake_case)]
+mod ffi {
+    extern "system" {
+        pub fn WaitOnAddress(
+            address: *const core::ffi::c_void,
+            compare_address: *const core::ffi::c_void,
+            address_size: usize,
+            timeout_ms: u32,
+        ) -> i32;
+        pub fn WakeByAddressAll(address: *const core::ffi::c_void);
+    }
+}
+
+/// Blocks until `*word != expected` or `timeout` elapses.
+///
+/// # Safety
+/// `word` must point to a valid, live `u32` for the duration of the call.
+#[cfg(windows)]
+pub(crate) unsafe fn futex_wait(word: *const u32, expected: u32, timeout: Option<Duration>) {
+    // INFINITE per the Win32 API; WaitOnAddress has no separate no-timeout
+    // sentinel distinct from the max u32 millisecond count.
+    let timeout_ms = timeout.map_or(u32::MAX, |d| d.as_millis().min(u32::MAX as u128) as u32);
+    let expected = expected; // address of the local copy compared against `word`
+    unsafe {
+        ffi::WaitOnAddress(
+            word as *const core::ffi::c_void,
+            &expected as *const u32 as *const core::ffi::c_void,
+            std::mem::size_of::<u32>(),
+            timeout_ms,
+        );
+    }
+}
+
+/// Wakes every thread parked on `word` via `futex_wait`.
+///
+/// # Safety
+/// `word` must point to a valid, live `u32`.
+#[cfg(windows)]
+pub(crate) unsafe fn futex_wake(word: *const u32, _n: i32) {
+    unsafe { ffi::WakeByAddressAll(word as *const core::ffi::c_void) };
+}
+
+/// Portable fallback: no cross-process wakeup primitive, so just sleep for
+/// a short bounded interval and let the caller's poll loop retry.
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) unsafe fn futex_wait(_word: *const u32, _expected: u32, timeout: Option<Duration>) {
+    let step = Duration::from_millis(1);
+    std::thread::sleep(timeout.map_or(step, |d| d.min(step)));
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) unsafe fn futex_wake(_word: *const u32, _n: i32) {}
@@ -48,11 +48,36 @@
 //! - `ring`: Ring buffer configuration and index arithmetic
 //! - `seqlock`: Sequence lock for lock-free reader/writer synchronization
 //! - `shm_layout`: Shared memory binary layout definitions
+//! - `codec`: Stable wire encoding for bus items, independent of in-memory layout
+//! - `journal`: Append-only on-disk capture/replay of a broadcast bus
+//! - `direct_journal`: O_DIRECT, page-aligned variant of `journal` with per-page CRCs
+//! - `byte_ring`: Variable-length framed ring for payloads with no fixed `T`
+//! - `futex`: Linux futex wakeups backing blocking reads
+//! - `stream` (feature `async-stream`): `futures_core::Stream` adapter over `BroadcastReader`
+//! - `bridge`: TCP fan-out of a local ring to remote subscribers
 
+mod bridge;
 mod broadcast;
+mod byte_ring;
+mod codec;
+mod direct_journal;
+mod futex;
+mod journal;
 mod ring;
 mod seqlock;
 mod shm_layout;
+#[cfg(feature = "async-stream")]
+mod stream;
 
-pub use broadcast::{BroadcastReader, BroadcastWriter};
-pub use ring::RingConfig;
+pub use bridge::{BridgeReader, BridgeServer};
+pub use broadcast::{
+    Backpressure, BroadcastReader, BroadcastWriter, MultiWriter, RateLimited, ReadOutcome, RingError,
+};
+pub use byte_ring::{ByteBroadcastReader, ByteBroadcastWriter};
+pub use codec::BusCodec;
+pub use direct_journal::{DirectJournal, DirectJournalReplayer, PAGE_SIZE};
+pub use journal::{Journal, JournalReader, ReplaySpeed};
+pub use ring::{RateLimit, RingConfig};
+pub use shm_layout::{ConsumerScan, MAX_READERS, ReaderLag};
+#[cfg(feature = "async-stream")]
+pub use stream::{BroadcastStream, StreamItem};
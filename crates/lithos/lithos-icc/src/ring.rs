@@ -13,6 +13,23 @@
 pub struct RingConfig {
     /// Number of slots in the ring. Must be a power of 2.
     pub capacity: usize,
+    /// When `true`, `BroadcastWriter::try_publish` refuses to lap registered
+    /// readers instead of overwriting their unread slots. Opt-in: `publish()`
+    /// stays lossy regardless of this setting.
+    pub bounded: bool,
+    /// Optional token-bucket ceiling for `BroadcastWriter::publish_paced` /
+    /// `try_publish_paced`. `None` means no pacing.
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// Token-bucket parameters for paced publishing.
+#[derive(Debug, Copy, Clone)]
+pub struct RateLimit {
+    /// Sustained publish rate, in messages per second.
+    pub rate: f64,
+    /// Maximum number of tokens the bucket can hold, i.e. how large a burst
+    /// above the sustained rate is allowed before pacing kicks in.
+    pub burst: f64,
 }
 
 impl RingConfig {
@@ -29,7 +46,52 @@ impl RingConfig {
     /// ```
     pub fn new(capacity: usize) -> Self {
         assert!(capacity.is_power_of_two(), "Capacity must be power of 2");
-        Self { capacity }
+        Self {
+            capacity,
+            bounded: false,
+            rate_limit: None,
+        }
+    }
+
+    /// Enables bounded (back-pressure) publish mode for this ring.
+    ///
+    /// # Example
+    /// ```
+    /// use lithos_icc::RingConfig;
+    /// let cfg = RingConfig::new(1024).bounded();
+    /// assert!(cfg.bounded);
+    /// ```
+    pub fn bounded(mut self) -> Self {
+        self.bounded = true;
+        self
+    }
+
+    /// Caps `publish_paced`/`try_publish_paced` at `rate` messages/sec, with
+    /// bursts of up to `burst` messages before pacing kicks in.
+    ///
+    /// # Example
+    /// ```
+    /// use lithos_icc::RingConfig;
+    /// let cfg = RingConfig::new(1024).rate_limited(1000.0, 50.0);
+    /// assert!(cfg.rate_limit.is_some());
+    /// ```
+    pub fn rate_limited(mut self, rate: f64, burst: f64) -> Self {
+        self.rate_limit = Some(RateLimit { rate, burst });
+        self
+    }
+
+    /// Convenience over `rate_limited` for the common case of a flat
+    /// events-per-second cap with one second's worth of burst allowance
+    /// (`burst == events_per_sec`).
+    ///
+    /// # Example
+    /// ```
+    /// use lithos_icc::RingConfig;
+    /// let cfg = RingConfig::new(1024).with_rate_limit(1000.0);
+    /// assert!(cfg.rate_limit.is_some());
+    /// ```
+    pub fn with_rate_limit(self, events_per_sec: f64) -> Self {
+        self.rate_limited(events_per_sec, events_per_sec)
     }
 
     /// Returns the bitmask for efficient index calculation.
@@ -21,6 +21,11 @@
 //!
 //! - **Pros**: Lock-free, no blocking, excellent for read-heavy workloads
 //! - **Cons**: Readers may spin during writes, requires `Copy` data
+//!
+//! [`SeqlockBytesSlot`] applies the same protocol to a variable-length byte
+//! payload (up to a fixed `CAP`) instead of a fixed `Copy` `T`, for messages
+//! like order-book deltas or trade prints whose size isn't known until
+//! publish time.
 
 use std::mem::MaybeUninit;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -139,4 +144,123 @@ impl<T: Copy> SeqlockSlot<T> {
             std::hint::spin_loop();
         }
     }
+
+    /// Like `read`, but gives up after `max_spins` failed attempts instead
+    /// of spinning forever, and distinguishes *why* it gave up.
+    ///
+    /// Intended for the defensive (`BroadcastReader::open_validated`)
+    /// reader path, where the mapped file might not have a well-behaved
+    /// writer on the other end: a crashed or stuck writer leaves `seq`
+    /// stuck odd, while a genuinely torn read (our copy straddled a full
+    /// write we never saw start) is a sharper sign of a corrupt region.
+    ///
+    /// # Returns
+    /// - `Ok(Some(value))`: a consistent read completed within the budget.
+    /// - `Ok(None)`: every attempt saw `seq` odd (write appears stuck).
+    /// - `Err(())`: `seq` changed to a different *even* value between our
+    ///   two loads without us observing the odd transition — the data we
+    ///   copied was torn by a write we raced with but never saw start.
+    #[inline(always)]
+    pub fn try_read_bounded(&self, max_spins: u32) -> Result<Option<T>, ()> {
+        for _ in 0..max_spins {
+            let s1 = self.seq.load(Ordering::Acquire);
+            if (s1 & 1) == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            // SAFETY: data is initialized after first write; consistency verified below
+            let v = unsafe { self.data.as_ptr().read() };
+
+            let s2 = self.seq.load(Ordering::Acquire);
+            if s1 == s2 {
+                return Ok(Some(v));
+            }
+            if (s2 & 1) == 1 {
+                // A new write started during our copy; may still resolve on
+                // a later spin.
+                std::hint::spin_loop();
+                continue;
+            }
+            return Err(());
+        }
+        Ok(None)
+    }
+}
+
+/// A seqlock-protected slot holding a variable-length byte payload, up to
+/// `CAP` bytes, instead of a fixed `Copy` type.
+///
+/// Same odd/even protocol as [`SeqlockSlot`]: the writer marks `seq` odd,
+/// copies `len` and the payload, then marks `seq` even; a reader spins past
+/// odd values and retries if `seq` changed out from under it. Unlike
+/// `SeqlockSlot<T>`, the payload is a plain `[u8; CAP]` rather than
+/// `MaybeUninit<T>` — any byte pattern is a valid `u8`, so a read that races
+/// a write and copies a torn mix of old/new bytes is harmless on its own;
+/// the `len` field married to the same `seq` check is what makes the
+/// *meaning* of those bytes consistent.
+#[repr(C, align(64))]
+pub struct SeqlockBytesSlot<const CAP: usize> {
+    /// Sequence counter: odd = write in progress, even = stable.
+    seq: AtomicU64,
+    /// Length of the payload currently in `data`, in `0..=CAP`.
+    len: u32,
+    _pad: u32,
+    data: [u8; CAP],
+}
+
+impl<const CAP: usize> SeqlockBytesSlot<CAP> {
+    /// Initializes the slot to a clean, empty state.
+    #[inline(always)]
+    pub fn init(&mut self) {
+        self.seq.store(0, Ordering::Relaxed);
+        self.len = 0;
+    }
+
+    /// Writes `value` to the slot using the seqlock protocol.
+    ///
+    /// # Panics
+    /// Panics if `value.len() > CAP`.
+    ///
+    /// # Single-Writer Per Slot
+    /// Same restriction as `SeqlockSlot::write`: not safe for multiple
+    /// writers on the same slot concurrently.
+    #[inline(always)]
+    pub fn write(&mut self, value: &[u8]) {
+        assert!(
+            value.len() <= CAP,
+            "payload of {} bytes exceeds SeqlockBytesSlot<{CAP}> capacity",
+            value.len()
+        );
+        let s0 = self.seq.load(Ordering::Relaxed);
+        self.seq.store(s0.wrapping_add(1), Ordering::Release);
+        self.len = value.len() as u32;
+        self.data[..value.len()].copy_from_slice(value);
+        self.seq.store(s0.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Reads a consistent snapshot into `out`, spinning until a stable read
+    /// completes, and returns the number of bytes copied — the validated
+    /// payload length, capped at `out.len()` if `out` is smaller.
+    #[inline(always)]
+    pub fn read_into(&self, out: &mut [u8]) -> usize {
+        loop {
+            let s1 = self.seq.load(Ordering::Acquire);
+            if (s1 & 1) == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let len = (self.len as usize).min(CAP);
+            let n = len.min(out.len());
+            out[..n].copy_from_slice(&self.data[..n]);
+
+            let s2 = self.seq.load(Ordering::Acquire);
+            if s1 == s2 && (s2 & 1) == 0 {
+                return n;
+            }
+
+            std::hint::spin_loop();
+        }
+    }
 }
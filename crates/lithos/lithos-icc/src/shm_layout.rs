@@ -7,11 +7,17 @@
 //!
 //! # Memory Layout
 //!
-//! Header fits in one cache line (64 bytes) so it does not share a line with slot[0].
-//!
 //! ```text
 //! ┌────────────────────────────────────────────────────────────────┐
-//! │  magic │ version │ capacity │ elem_size │ write_seq │   pad    │  (64 B)
+//! │  magic │ version │ capacity │ elem_size │ flags │  (padding)   │  (64 B) Metadata — immutable
+//! ├────────────────────────────────────────────────────────────────┤
+//! │  write_seq                │             (padding)             │  (64 B) WriteCursor — writer-hot
+//! ├────────────────────────────────────────────────────────────────┤
+//! │  cached_min_read_seq │ waiters │         (padding)             │  (64 B) BackpressureCache
+//! ├────────────────────────────────────────────────────────────────┤
+//! │  next_correlation_id       │             (padding)             │  (64 B) CorrelationCounter
+//! ├────────────────────────────────────────────────────────────────┤
+//! │                  ReaderSlot[0..MAX_READERS)                    │  (64 B each)
 //! ├────────────────────────────────────────────────────────────────┤
 //! │                     SeqlockSlot[0]                             │
 //! │  ┌──────────────────┬─────────────────────────────────────┐    │
@@ -25,10 +31,18 @@
 //! │                  SeqlockSlot[capacity-1]                       │
 //! └────────────────────────────────────────────────────────────────┘
 //! ```
+//!
+//! Each top-level section is its own `#[repr(C, align(64))]` type, the same
+//! device [`ReaderSlot`] already uses for the consumer registry: giving
+//! `write_seq` its own cache line means the writer's `fetch_add` and
+//! readers' `Acquire` loads of it no longer invalidate `flags`/`magic` (or
+//! vice versa) on every publish, following the tail/head/correlation layout
+//! Aeron uses for the same reason.
 
 use crate::seqlock::SeqlockSlot;
 use std::mem::size_of;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Magic number identifying a valid ring buffer file.
 ///
@@ -43,50 +57,415 @@ pub const RING_MAGIC: u64 = 0x4C49_5448_4F53_4255;
 ///
 /// Increment this when making incompatible changes to the layout.
 /// Readers will reject files with mismatched versions.
-pub const RING_VERSION: u64 = 3;
+pub const RING_VERSION: u64 = 7;
 
-/// Header structure at the start of every ring buffer.
+/// Bit in [`RingHeader::flags`] indicating bounded (back-pressure) publish
+/// mode is enabled for this ring.
+const FLAG_BOUNDED: u64 = 1 << 0;
+
+/// Maximum number of readers that can be registered in the consumer
+/// registry at once. Chosen generously for IPC fan-out without making the
+/// header unreasonably large (`MAX_READERS * 64` bytes).
+pub const MAX_READERS: usize = 32;
+
+/// `ReaderSlot::in_use` value of a registry slot no reader currently owns.
+const SLOT_FREE: u64 = 0;
+const SLOT_IN_USE: u64 = 1;
+
+/// Wall-clock time in nanoseconds since the Unix epoch.
 ///
-/// This header is stored at offset 0 in the memory-mapped region and contains
-/// all metadata needed for readers to validate and navigate the ring buffer.
+/// Heartbeats are compared across processes (writer vs. reader), so a
+/// per-process monotonic clock like `Instant` won't do; this uses
+/// `SystemTime`, which is consistent across processes on the same host.
+pub(crate) fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// One reader's liveness entry in the consumer registry.
 ///
-/// # Representation
-/// Uses `#[repr(C)]` to ensure predictable field ordering and alignment.
-/// Fits in one cache line (64 bytes) so the header never false-shares with slot[0].
-#[repr(C)]
-pub struct RingHeader {
-    /// Magic number for file type identification. Must equal `RING_MAGIC`.
-    pub magic: u64,
+/// Cache-line padded (`align(64)`) so a reader updating its own slot and the
+/// writer scanning every slot for `reader_lag()` don't false-share a line
+/// with each other or with neighboring slots.
+#[repr(C, align(64))]
+struct ReaderSlot {
+    /// `SLOT_FREE` if unclaimed, `SLOT_IN_USE` once a reader owns this slot.
+    in_use: AtomicU64,
+    /// The reader's last-published consume position.
+    read_seq: AtomicU64,
+    /// Wall-clock timestamp (nanoseconds) of the reader's last heartbeat.
+    last_heartbeat_ns: AtomicU64,
+}
 
-    /// Format version for compatibility checking. Must equal `RING_VERSION`.
-    pub version: u64,
+impl ReaderSlot {
+    const fn new() -> Self {
+        Self {
+            in_use: AtomicU64::new(SLOT_FREE),
+            read_seq: AtomicU64::new(0),
+            last_heartbeat_ns: AtomicU64::new(0),
+        }
+    }
+}
 
-    /// Number of slots in the ring. Must be a power of 2.
-    pub capacity: u64,
+/// Per-slot lag and liveness snapshot returned by `BroadcastWriter::reader_lag`.
+#[derive(Debug, Clone, Copy)]
+pub struct ReaderLag {
+    /// Registry slot index (stable for the life of the reader).
+    pub slot: usize,
+    /// `write_seq - read_seq`: how many unread items are behind the writer.
+    pub lag: u64,
+    /// Nanoseconds since this reader's last heartbeat.
+    pub since_heartbeat_ns: u64,
+}
 
+/// Result of `RingHeader::scan_consumers`: every currently-claimed reader
+/// slot classified as live or stale against a caller-supplied heartbeat
+/// timeout.
+#[derive(Debug, Clone)]
+pub struct ConsumerScan {
+    /// Readers whose last heartbeat is within the timeout.
+    pub live: Vec<ReaderLag>,
+    /// Registry slot indices whose last heartbeat is older than the
+    /// timeout — candidates for `RingHeader::reclaim_stale`.
+    pub stale: Vec<usize>,
+    /// Minimum `read_seq` across `live` only (a stale reader's position is
+    /// not allowed to hold back reclamation of its own slot), or
+    /// `write_seq` if no reader is live.
+    pub min_live_read_seq: u64,
+}
+
+/// Immutable ring metadata, set once at creation and never mutated
+/// afterward. Isolated on its own cache line so a writer hammering
+/// `write_seq` never invalidates it (or vice versa) for a reader that's
+/// just validating the header.
+#[repr(C, align(64))]
+struct Metadata {
+    /// Magic number for file type identification. Must equal `RING_MAGIC`.
+    magic: u64,
+    /// Format version for compatibility checking. Must equal `RING_VERSION`.
+    version: u64,
+    /// Number of slots in the ring. Must be a power of 2.
+    capacity: u64,
     /// Size of each element in bytes. Used to verify type compatibility.
-    pub elem_size: u64,
+    elem_size: u64,
+    /// Bit flags for ring-wide options (see `FLAG_BOUNDED`).
+    flags: u64,
+}
 
+/// The writer's publish position, alone on its own cache line.
+///
+/// This is the hottest word in the header: every `publish()` does a
+/// `fetch_add` on it, and every reader polls it with an `Acquire` load.
+/// Giving it a dedicated line (rather than packing it next to metadata or
+/// the back-pressure cache, as earlier versions of this header did) means
+/// that traffic doesn't false-share with anything else.
+#[repr(C, align(64))]
+struct WriteCursor {
     /// Monotonically increasing count of published items.
     /// Writers increment this atomically; readers use it to detect new data.
-    pub write_seq: AtomicU64,
+    write_seq: AtomicU64,
+}
+
+/// Bounded-publish back-pressure state, cache-line isolated from
+/// `write_seq` since it's updated on a different cadence (only when the
+/// cache looks stale, vs. every publish).
+#[repr(C, align(64))]
+struct BackpressureCache {
+    /// Cached lower bound on the minimum `read_seq` across registered
+    /// readers, used by bounded publish to avoid scanning the full registry
+    /// on every call. Only meaningful when `FLAG_BOUNDED` is set.
+    cached_min_read_seq: AtomicU64,
+    /// Count of readers currently parked in `read_blocking`. The writer
+    /// checks this before issuing a `FUTEX_WAKE` so a publish with no
+    /// parked readers costs one relaxed load, not a syscall.
+    waiters: AtomicU64,
+}
 
-    /// Padding to end of first cache line (64 bytes). Header and slot[0] stay on separate lines.
-    _pad: [u8; 24],
+/// Cross-process correlation-ID allocator, mirroring Aeron's
+/// correlation-counter slot. Cache-line isolated like `WriteCursor` since
+/// `next_correlation_id` can be incremented independently of (and just as
+/// often as) `write_seq`.
+#[repr(C, align(64))]
+struct CorrelationCounter {
+    next_correlation_id: AtomicU64,
+}
+
+/// Header structure at the start of every ring buffer.
+///
+/// This header is stored at offset 0 in the memory-mapped region and contains
+/// all metadata needed for readers to validate and navigate the ring buffer,
+/// plus the consumer registry used for liveness and lag monitoring.
+///
+/// # Representation
+/// Uses `#[repr(C)]` over cache-line-aligned sub-structs (see [`Metadata`],
+/// [`WriteCursor`], [`BackpressureCache`], [`CorrelationCounter`]) so each
+/// logically-distinct piece of state lives on its own 64-byte line instead
+/// of false-sharing a line with unrelated fields.
+#[repr(C)]
+pub struct RingHeader {
+    meta: Metadata,
+    write_cursor: WriteCursor,
+    backpressure: BackpressureCache,
+    correlation: CorrelationCounter,
+    /// Consumer registry: one slot per potential reader.
+    readers: [ReaderSlot; MAX_READERS],
 }
 
 impl RingHeader {
     /// Constructs a new header for ring creation. Callers must set `write_seq` via
     /// the returned header; this only initializes the static fields and padding.
-    pub fn new(magic: u64, version: u64, capacity: u64, elem_size: u64) -> Self {
+    pub fn new(magic: u64, version: u64, capacity: u64, elem_size: u64, bounded: bool) -> Self {
         Self {
-            magic,
-            version,
-            capacity,
-            elem_size,
-            write_seq: AtomicU64::new(0),
-            _pad: [0; 24],
+            meta: Metadata {
+                magic,
+                version,
+                capacity,
+                elem_size,
+                flags: if bounded { FLAG_BOUNDED } else { 0 },
+            },
+            write_cursor: WriteCursor {
+                write_seq: AtomicU64::new(0),
+            },
+            backpressure: BackpressureCache {
+                cached_min_read_seq: AtomicU64::new(0),
+                waiters: AtomicU64::new(0),
+            },
+            correlation: CorrelationCounter {
+                next_correlation_id: AtomicU64::new(0),
+            },
+            readers: std::array::from_fn(|_| ReaderSlot::new()),
+        }
+    }
+
+    /// Magic number for file type identification.
+    pub fn magic(&self) -> u64 {
+        self.meta.magic
+    }
+
+    /// Format version for compatibility checking.
+    pub fn version(&self) -> u64 {
+        self.meta.version
+    }
+
+    /// Number of slots in the ring.
+    pub fn capacity(&self) -> u64 {
+        self.meta.capacity
+    }
+
+    /// Size of each element in bytes.
+    pub fn elem_size(&self) -> u64 {
+        self.meta.elem_size
+    }
+
+    /// The writer's publish-position counter.
+    pub(crate) fn write_seq(&self) -> &AtomicU64 {
+        &self.write_cursor.write_seq
+    }
+
+    /// Raw pointer to the low 32 bits of `write_seq`, used as the futex word
+    /// for `read_blocking`/`publish`'s wakeup.
+    ///
+    /// Truncating a monotonically increasing `u64` to its low 32 bits can
+    /// only produce a false "unchanged" reading once every 2^32 publishes,
+    /// at which point the reader's own `try_read` comparison (done before
+    /// and after the wait) still catches the new data; the futex word only
+    /// needs to change *often enough* to avoid indefinite sleeps, not on
+    /// every single publish.
+    pub(crate) fn futex_word_ptr(&self) -> *const u32 {
+        (&self.write_cursor.write_seq as *const AtomicU64) as *const u32
+    }
+
+    /// Marks one more reader as parked in `read_blocking`.
+    pub(crate) fn register_waiter(&self) {
+        self.backpressure.waiters.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Marks a reader as no longer parked.
+    pub(crate) fn unregister_waiter(&self) {
+        self.backpressure.waiters.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Whether any reader is currently parked in `read_blocking`.
+    pub(crate) fn has_waiters(&self) -> bool {
+        self.backpressure.waiters.load(Ordering::SeqCst) > 0
+    }
+
+    /// Whether this ring was created with bounded (back-pressure) publish
+    /// mode enabled.
+    pub(crate) fn is_bounded(&self) -> bool {
+        self.meta.flags & FLAG_BOUNDED != 0
+    }
+
+    /// Full scan for the minimum `read_seq` across every currently-claimed
+    /// reader slot, or `write_seq` (no lag) if no readers are registered.
+    ///
+    /// This is the expensive path; bounded `try_publish` only calls it when
+    /// the cached value looks stale.
+    pub(crate) fn min_reader_seq(&self) -> u64 {
+        self.readers
+            .iter()
+            .filter(|slot| slot.in_use.load(Ordering::Acquire) == SLOT_IN_USE)
+            .map(|slot| slot.read_seq.load(Ordering::Acquire))
+            .min()
+            .unwrap_or_else(|| self.write_cursor.write_seq.load(Ordering::Acquire))
+    }
+
+    /// Relaxed load of the cached minimum reader position.
+    pub(crate) fn cached_min_read_seq(&self) -> u64 {
+        self.backpressure.cached_min_read_seq.load(Ordering::Relaxed)
+    }
+
+    /// Relaxed store refreshing the cached minimum reader position.
+    pub(crate) fn set_cached_min_read_seq(&self, value: u64) {
+        self.backpressure.cached_min_read_seq.store(value, Ordering::Relaxed);
+    }
+
+    /// Allocates the next correlation ID from the shared, cross-process
+    /// counter. Ordering is `Relaxed`: callers only need uniqueness and
+    /// monotonicity per-allocator, not a happens-before relationship with
+    /// any other header field.
+    pub(crate) fn next_correlation_id(&self) -> u64 {
+        self.correlation.next_correlation_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Reads the counter's current value without allocating an ID. Used by
+    /// the read-only accessor on `BroadcastReader`: readers correlate
+    /// against IDs the writer already allocated, they don't allocate their
+    /// own.
+    pub(crate) fn peek_correlation_id(&self) -> u64 {
+        self.correlation.next_correlation_id.load(Ordering::Relaxed)
+    }
+
+    /// Claims the first free reader slot, returning its index.
+    ///
+    /// Returns `None` if every slot in the registry is already claimed.
+    pub(crate) fn claim_reader_slot(&self) -> Option<usize> {
+        for (idx, slot) in self.readers.iter().enumerate() {
+            if slot
+                .in_use
+                .compare_exchange(
+                    SLOT_FREE,
+                    SLOT_IN_USE,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                slot.read_seq.store(0, Ordering::Relaxed);
+                slot.last_heartbeat_ns.store(now_ns(), Ordering::Relaxed);
+                return Some(idx);
+            }
+        }
+        None
+    }
+
+    /// Releases a previously-claimed reader slot, freeing it for reuse.
+    pub(crate) fn release_reader_slot(&self, idx: usize) {
+        self.readers[idx].in_use.store(SLOT_FREE, Ordering::Release);
+    }
+
+    /// Publishes `read_seq` into the reader's slot with `Release` ordering so
+    /// the writer's lag scan observes progress promptly.
+    pub(crate) fn publish_read_seq(&self, idx: usize, read_seq: u64) {
+        self.readers[idx].read_seq.store(read_seq, Ordering::Release);
+    }
+
+    /// Stamps the reader's slot with the current time, marking it alive.
+    pub(crate) fn heartbeat(&self, idx: usize) {
+        self.readers[idx].last_heartbeat_ns.store(now_ns(), Ordering::Relaxed);
+    }
+
+    /// Returns `(write_seq - read_seq, since_heartbeat_ns)` for every
+    /// currently-claimed reader slot.
+    pub(crate) fn reader_lags(&self) -> Vec<ReaderLag> {
+        let w = self.write_cursor.write_seq.load(Ordering::Acquire);
+        let now = now_ns();
+        self.readers
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.in_use.load(Ordering::Acquire) == SLOT_IN_USE)
+            .map(|(idx, slot)| {
+                let read_seq = slot.read_seq.load(Ordering::Acquire);
+                let last_heartbeat = slot.last_heartbeat_ns.load(Ordering::Relaxed);
+                ReaderLag {
+                    slot: idx,
+                    lag: w.saturating_sub(read_seq),
+                    since_heartbeat_ns: now.saturating_sub(last_heartbeat),
+                }
+            })
+            .collect()
+    }
+
+    /// Classifies every currently-claimed reader slot as live or stale
+    /// against `timeout_ns`, and computes the minimum `read_seq` across the
+    /// live ones only.
+    ///
+    /// This is the full-scan counterpart to `reader_lags`: a supervisor uses
+    /// it to tell a slow-but-alive reader (still counted for back-pressure)
+    /// apart from a dead one (safe to reclaim via `reclaim_stale`).
+    pub(crate) fn scan_consumers(&self, timeout_ns: u64) -> ConsumerScan {
+        let w = self.write_cursor.write_seq.load(Ordering::Acquire);
+        let now = now_ns();
+        let mut live = Vec::new();
+        let mut stale = Vec::new();
+        let mut min_live_read_seq = w;
+
+        for (idx, slot) in self.readers.iter().enumerate() {
+            if slot.in_use.load(Ordering::Acquire) != SLOT_IN_USE {
+                continue;
+            }
+            let read_seq = slot.read_seq.load(Ordering::Acquire);
+            let last_heartbeat = slot.last_heartbeat_ns.load(Ordering::Relaxed);
+            let since_heartbeat_ns = now.saturating_sub(last_heartbeat);
+
+            if since_heartbeat_ns > timeout_ns {
+                stale.push(idx);
+            } else {
+                min_live_read_seq = min_live_read_seq.min(read_seq);
+                live.push(ReaderLag {
+                    slot: idx,
+                    lag: w.saturating_sub(read_seq),
+                    since_heartbeat_ns,
+                });
+            }
+        }
+
+        ConsumerScan {
+            live,
+            stale,
+            min_live_read_seq,
+        }
+    }
+
+    /// Reclaims every reader slot whose last heartbeat is older than
+    /// `timeout_ns`, freeing it for a new reader to claim. Returns the
+    /// number of slots reclaimed.
+    ///
+    /// Uses a CAS from `SLOT_IN_USE` to `SLOT_FREE` rather than an
+    /// unconditional store, so a reader that heartbeats concurrently with
+    /// the scan can still win the race and keep its slot.
+    pub(crate) fn reclaim_stale(&self, timeout_ns: u64) -> usize {
+        let now = now_ns();
+        let mut reclaimed = 0;
+        for slot in self.readers.iter() {
+            if slot.in_use.load(Ordering::Acquire) != SLOT_IN_USE {
+                continue;
+            }
+            let last_heartbeat = slot.last_heartbeat_ns.load(Ordering::Relaxed);
+            if now.saturating_sub(last_heartbeat) <= timeout_ns {
+                continue;
+            }
+            if slot
+                .in_use
+                .compare_exchange(SLOT_IN_USE, SLOT_FREE, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                reclaimed += 1;
+            }
         }
+        reclaimed
     }
 
     /// Validates the header against expected values.
@@ -110,16 +489,16 @@ impl RingHeader {
     /// header.validate::<MyEventType>()?;
     /// ```
     pub fn validate<T: Copy>(&self) -> Result<(), &'static str> {
-        if self.magic != RING_MAGIC {
+        if self.meta.magic != RING_MAGIC {
             return Err("Bad magic");
         }
-        if self.version != RING_VERSION {
+        if self.meta.version != RING_VERSION {
             return Err("Wrong version");
         }
-        if (self.capacity as usize).is_power_of_two() == false {
+        if (self.meta.capacity as usize).is_power_of_two() == false {
             return Err("Capacity must be power of two");
         }
-        if self.elem_size as usize != size_of::<T>() {
+        if self.meta.elem_size as usize != size_of::<T>() {
             return Err("Element size mismatch");
         }
 
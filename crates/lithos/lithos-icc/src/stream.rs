@@ -0,0 +1,130 @@
+//! `futures_core::Stream` adapter for `BroadcastReader`, so an async
+//! runtime (tokio, smol) can consume the ring with `.next().await` instead
+//! of spin-polling `try_read()`.
+//!
+//! The crate's wakeup primitive (the futex word in `RingHeader`, see
+//! `BroadcastReader::read_blocking`) is a synchronous parking mechanism, not
+//! a reactor source a `Waker` can register against directly. `into_stream()`
+//! bridges the two by spawning one dedicated thread per stream that calls
+//! `read_blocking` in a loop and relays each result to the polling task;
+//! this costs one parked OS thread per stream rather than a true
+//! epoll/io_uring registration, but needs no new reactor integration and
+//! reuses `read_blocking`'s exact wakeup path.
+
+use crate::broadcast::BroadcastReader;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+
+/// One item relayed from the background relay thread to the polling task:
+/// either a read value or a detected overrun, so skips are observable in
+/// the async path without a separate call to `overruns()`. Covers both the
+/// underlying ring's own overrun tracking and messages lost to the relay's
+/// one-slot mailbox being overwritten before a poll drained it.
+#[derive(Debug, Clone, Copy)]
+pub enum StreamItem<T> {
+    Value(T),
+    /// Number of messages skipped since the last item, detected the same
+    /// way `BroadcastReader::overruns()` tracks it.
+    Overrun(u64),
+}
+
+struct Shared<T> {
+    next: Mutex<Option<StreamItem<T>>>,
+    waker: Mutex<Option<Waker>>,
+    /// Count of messages lost to the one-slot mailbox in `next` being
+    /// overwritten before `poll_next` drained it — on top of (and folded
+    /// together with) whatever `BroadcastReader::overruns()` itself already
+    /// detected, so a slow poller's losses show up the same way a slow
+    /// reader's do instead of vanishing silently.
+    dropped: AtomicU64,
+}
+
+/// An async stream over a `BroadcastReader`. Created via
+/// `BroadcastReader::into_stream`.
+pub struct BroadcastStream<T: Copy + Send + 'static> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Copy + Send + 'static> BroadcastReader<T> {
+    /// Converts this reader into a `futures_core::Stream`, spawning a
+    /// background thread that drives `read_blocking` and relays items to
+    /// whichever task is polling the stream.
+    pub fn into_stream(mut self) -> BroadcastStream<T> {
+        let shared = Arc::new(Shared {
+            next: Mutex::new(None),
+            waker: Mutex::new(None),
+            dropped: AtomicU64::new(0),
+        });
+        let relay = Arc::clone(&shared);
+
+        std::thread::spawn(move || {
+            loop {
+                let before = self.overruns();
+                let item = match self.read_blocking(None) {
+                    Some(v) => {
+                        let after = self.overruns();
+                        if after > before {
+                            // Messages skipped before `v` go through `dropped`,
+                            // which `poll_next` reports ahead of `v` itself.
+                            relay.dropped.fetch_add(after - before, Ordering::Relaxed);
+                        }
+                        StreamItem::Value(v)
+                    }
+                    // `read_blocking(None)` waits with no deadline and does
+                    // not return `None`; this arm only ends the relay loop.
+                    None => break,
+                };
+
+                let mut slot = relay.next.lock().unwrap();
+                if let Some(overwritten) = slot.take() {
+                    // The poller hasn't drained the mailbox since the last
+                    // relay; folding what's about to be overwritten into
+                    // `dropped` keeps it from disappearing without a trace.
+                    let lost = match overwritten {
+                        StreamItem::Value(_) => 1,
+                        StreamItem::Overrun(n) => n,
+                    };
+                    relay.dropped.fetch_add(lost, Ordering::Relaxed);
+                }
+                *slot = Some(item);
+                drop(slot);
+                if let Some(waker) = relay.waker.lock().unwrap().take() {
+                    waker.wake();
+                }
+            }
+        });
+
+        BroadcastStream { shared }
+    }
+}
+
+impl<T: Copy + Send + 'static> futures_core::Stream for BroadcastStream<T> {
+    type Item = StreamItem<T>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        // Report a mailbox overwrite before the item that overwrote it, so
+        // the count comes back at most one poll late rather than never.
+        let dropped = self.shared.dropped.swap(0, Ordering::AcqRel);
+        if dropped > 0 {
+            return std::task::Poll::Ready(Some(StreamItem::Overrun(dropped)));
+        }
+        if let Some(item) = self.shared.next.lock().unwrap().take() {
+            return std::task::Poll::Ready(Some(item));
+        }
+        *self.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+        // Re-check after registering the waker to close the race where the
+        // relay thread delivered an item between our first check and now.
+        let dropped = self.shared.dropped.swap(0, Ordering::AcqRel);
+        if dropped > 0 {
+            return std::task::Poll::Ready(Some(StreamItem::Overrun(dropped)));
+        }
+        if let Some(item) = self.shared.next.lock().unwrap().take() {
+            return std::task::Poll::Ready(Some(item));
+        }
+        std::task::Poll::Pending
+    }
+}
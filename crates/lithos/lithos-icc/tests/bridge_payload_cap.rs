@@ -0,0 +1,41 @@
+//! Regression test: `BridgeReader::relay_one` must reject an oversized frame
+//! length instead of driving a huge allocation. A malicious or corrupted
+//! remote peer controls the wire `len` field entirely, so the cap has to be
+//! enforced before `vec![0u8; len]`, not after.
+
+use lithos_events::TopOfBook;
+use lithos_icc::{BridgeReader, BroadcastWriter, RingConfig};
+use std::io::Write;
+use std::net::TcpListener;
+
+#[test]
+fn oversized_frame_length_is_rejected_not_allocated() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().unwrap();
+
+    let server = std::thread::spawn(move || {
+        let (mut socket, _) = listener.accept().expect("accept");
+        // [magic][seq][len] with `len` far beyond any real frame, and no
+        // payload bytes backing it — a well-behaved reject happens before
+        // ever trying to read (or allocate for) the payload.
+        socket.write_all(&0x4C54_4252u32.to_le_bytes()).unwrap();
+        socket.write_all(&0u64.to_le_bytes()).unwrap();
+        socket.write_all(&u32::MAX.to_le_bytes()).unwrap();
+    });
+
+    let mut reader = BridgeReader::connect(addr).expect("connect");
+    let ring_path = format!("/tmp/lithos_bridge_cap_{}", std::process::id());
+    let _ = std::fs::remove_file(&ring_path);
+    let mut writer =
+        BroadcastWriter::<TopOfBook>::create(&ring_path, RingConfig::new(64)).expect("create ring");
+
+    let result = reader.relay_one(&mut writer);
+    assert!(
+        result.is_err(),
+        "a frame claiming a {}-byte payload must be rejected, not allocated",
+        u32::MAX
+    );
+
+    server.join().unwrap();
+    let _ = std::fs::remove_file(&ring_path);
+}
@@ -0,0 +1,70 @@
+//! Regression test for `BroadcastStream`'s relay mailbox: a poller that's
+//! slower than the relay thread must never silently lose an item. Every
+//! message relayed from the ring either comes back as a `StreamItem::Value`
+//! or is folded into a `StreamItem::Overrun` count — so summing `1` per
+//! `Value` and `n` per `Overrun` across every poll must equal the number of
+//! records published, even when the stream is deliberately starved of polls
+//! while the writer races ahead.
+
+use futures_core::Stream;
+use lithos_icc::{BroadcastReader, BroadcastWriter, RingConfig, StreamItem};
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+fn test_path() -> String {
+    format!("/tmp/lithos_stream_overrun_{}", std::process::id())
+}
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        raw()
+    }
+    fn noop(_: *const ()) {}
+    fn raw() -> RawWaker {
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    unsafe { Waker::from_raw(raw()) }
+}
+
+#[test]
+fn starved_poller_accounts_for_every_published_record() {
+    let path = test_path();
+    let _ = std::fs::remove_file(&path);
+
+    const EVENT_COUNT: u64 = 2_000;
+
+    let mut writer =
+        BroadcastWriter::<u64>::create(&path, RingConfig::new(1 << 16)).expect("create ring");
+    let reader = BroadcastReader::<u64>::open(&path).expect("open ring");
+    let mut stream = reader.into_stream();
+
+    // Publish as fast as possible with no interleaved polling, so the relay
+    // thread (which drives its own read_blocking loop independently) races
+    // far ahead of the one-slot mailbox and is forced to overwrite it.
+    for i in 0..EVENT_COUNT {
+        writer.publish(i);
+    }
+
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut accounted: u64 = 0;
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+
+    while accounted < EVENT_COUNT && std::time::Instant::now() < deadline {
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(StreamItem::Value(_))) => accounted += 1,
+            Poll::Ready(Some(StreamItem::Overrun(n))) => accounted += n,
+            Poll::Ready(None) => break,
+            Poll::Pending => std::thread::yield_now(),
+        }
+    }
+
+    drop(writer);
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(
+        accounted, EVENT_COUNT,
+        "every published record must be accounted for as either a Value or folded into an Overrun count"
+    );
+}
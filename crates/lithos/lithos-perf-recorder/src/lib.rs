@@ -7,6 +7,23 @@
 //! When `record` is **on**, each stage gets a pre-allocated `[u64; MAX_SAMPLES]`
 //! ring (~20 MB total) and `begin`/`end` pairs store elapsed nanoseconds via
 //! `clock_gettime(CLOCK_MONOTONIC)`.
+//!
+//! `PerfRecorder::new_compressed()` picks a second storage mode instead:
+//! each sample is delta-encoded against the previous one, zigzag-mapped to
+//! unsigned, and LEB128 varint-packed into a growable `Vec<u8>`. Latency
+//! samples cluster tightly run to run, so most deltas fit in 1-2 bytes —
+//! typically 3-4x less memory than the raw mode for the same sample count,
+//! at the cost of a decode pass in `samples()` instead of a direct slice.
+//!
+//! `PerfRecorder::new_histogram()` picks a third mode for long captures
+//! where even compressed per-sample storage grows unbounded: a log-linear
+//! bucketed histogram with fixed memory that records in O(1) and answers
+//! `percentile()` queries in O(buckets) instead of sorting. It can't
+//! reconstruct the original sample stream, so `samples()` is empty in this
+//! mode — use `percentile()`/`max()`/`count()` instead.
+//!
+//! `PerfRecorder::new()` is untouched by either of the above and stays the
+//! zero-overhead raw path.
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -64,20 +81,296 @@ mod inner {
         (ts.tv_sec as u64) * 1_000_000_000 + ts.tv_nsec as u64
     }
 
-    struct StageBuf {
-        samples: Box<[u64; MAX_SAMPLES]>,
-        count: usize,
-        pending: u64,
+    /// Maps a signed delta to an unsigned value so small magnitudes (either
+    /// sign) stay small after varint packing: `0, -1, 1, -2, 2, ...`.
+    #[inline(always)]
+    fn zigzag_encode(delta: i64) -> u64 {
+        ((delta << 1) ^ (delta >> 63)) as u64
     }
 
-    impl StageBuf {
-        fn new() -> Self {
-            Self {
+    #[inline(always)]
+    fn zigzag_decode(z: u64) -> i64 {
+        ((z >> 1) as i64) ^ -((z & 1) as i64)
+    }
+
+    /// LEB128: 7 payload bits per byte, high bit set means "more bytes follow".
+    fn write_varint(mut v: u64, out: &mut Vec<u8>) {
+        loop {
+            let byte = (v & 0x7f) as u8;
+            v >>= 7;
+            if v == 0 {
+                out.push(byte);
+                break;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    /// Decodes the varbyte -> zigzag -> prefix-sum chain back into the
+    /// original `u64` sample stream.
+    struct CompressedSamples<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+        last_value: u64,
+    }
+
+    impl Iterator for CompressedSamples<'_> {
+        type Item = u64;
+
+        fn next(&mut self) -> Option<u64> {
+            if self.pos >= self.bytes.len() {
+                return None;
+            }
+            let mut result: u64 = 0;
+            let mut shift = 0;
+            loop {
+                let byte = self.bytes[self.pos];
+                self.pos += 1;
+                result |= ((byte & 0x7f) as u64) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                shift += 7;
+            }
+            self.last_value = self.last_value.wrapping_add(zigzag_decode(result) as u64);
+            Some(self.last_value)
+        }
+    }
+
+    /// Bits of sub-bucket resolution kept below the leading bit of each
+    /// power-of-two exponent, bounding relative error within a bucket to
+    /// about `1 / 2^SUB_BUCKET_BITS`.
+    const SUB_BUCKET_BITS: u32 = 5;
+    const SUB_BUCKET_COUNT: usize = 1 << SUB_BUCKET_BITS; // 32
+    /// One bucket row per bit of `u64`, so the histogram covers the full range.
+    const NUM_EXPONENTS: usize = 64;
+    /// Below this value, the leading bit is too low to leave `SUB_BUCKET_BITS`
+    /// of room beneath it, so the log-linear scheme below has nothing to key
+    /// sub-bucket resolution off of. Standard HdrHistogram low-range handling:
+    /// give every integer below here (`2 * SUB_BUCKET_COUNT` of them) its own
+    /// bucket instead of collapsing a whole exponent row into one.
+    const LOW_RANGE_BITS: u32 = SUB_BUCKET_BITS + 1;
+    const LOW_RANGE_VALUES: usize = 1 << LOW_RANGE_BITS; // 64
+    const NUM_BUCKETS: usize =
+        LOW_RANGE_VALUES + (NUM_EXPONENTS - LOW_RANGE_BITS as usize) * SUB_BUCKET_COUNT;
+
+    /// Maps a value to its histogram bucket. Values below `LOW_RANGE_VALUES`
+    /// get unit resolution (one bucket per integer); wider values use the
+    /// log-linear scheme, where the top bit picks the exponent row and the
+    /// next `SUB_BUCKET_BITS` bits below it pick the sub-bucket.
+    fn bucket_index(value: u64) -> usize {
+        let v = value.max(1);
+        if v < LOW_RANGE_VALUES as u64 {
+            return v as usize;
+        }
+        let exp = 63 - v.leading_zeros();
+        let shift = exp - SUB_BUCKET_BITS;
+        let sub = ((v >> shift) & (SUB_BUCKET_COUNT as u64 - 1)) as usize;
+        LOW_RANGE_VALUES + (exp - LOW_RANGE_BITS) as usize * SUB_BUCKET_COUNT + sub
+    }
+
+    /// Midpoint of the value range a bucket covers — the representative
+    /// value `percentile()` returns for that bucket. Inverse of `bucket_index`.
+    fn bucket_midpoint(idx: usize) -> u64 {
+        if idx < LOW_RANGE_VALUES {
+            return idx as u64;
+        }
+        let rel = idx - LOW_RANGE_VALUES;
+        let exp = LOW_RANGE_BITS + (rel / SUB_BUCKET_COUNT) as u32;
+        let sub = rel % SUB_BUCKET_COUNT;
+        let shift = exp - SUB_BUCKET_BITS;
+        let width = 1u64 << shift;
+        let lo = (1u64 << exp) | ((sub as u64) << shift);
+        lo + width / 2
+    }
+
+    fn percentile_of_sorted(sorted: &[u64], q: f64) -> u64 {
+        let len = sorted.len();
+        if len == 1 {
+            return sorted[0];
+        }
+        let rank = (q / 100.0 * len as f64).ceil() as usize;
+        sorted[rank.saturating_sub(1).min(len - 1)]
+    }
+
+    enum Storage {
+        Raw {
+            samples: Box<[u64; MAX_SAMPLES]>,
+            count: usize,
+        },
+        Compressed {
+            bytes: Vec<u8>,
+            last_value: u64,
+            count: usize,
+        },
+        Histogram {
+            buckets: Box<[u32; NUM_BUCKETS]>,
+            count: u64,
+            max: u64,
+        },
+    }
+
+    impl Storage {
+        fn raw() -> Self {
+            Self::Raw {
                 samples: vec![0u64; MAX_SAMPLES]
                     .into_boxed_slice()
                     .try_into()
                     .unwrap(),
                 count: 0,
+            }
+        }
+
+        fn compressed() -> Self {
+            Self::Compressed {
+                bytes: Vec::new(),
+                last_value: 0,
+                count: 0,
+            }
+        }
+
+        fn histogram() -> Self {
+            Self::Histogram {
+                buckets: vec![0u32; NUM_BUCKETS].into_boxed_slice().try_into().unwrap(),
+                count: 0,
+                max: 0,
+            }
+        }
+
+        fn push(&mut self, value: u64) {
+            match self {
+                Self::Raw { samples, count } => {
+                    if *count < MAX_SAMPLES {
+                        samples[*count] = value;
+                        *count += 1;
+                    }
+                }
+                Self::Compressed {
+                    bytes,
+                    last_value,
+                    count,
+                } => {
+                    // First sample is a delta from 0, so absolute values
+                    // fall out of the same encode path with no special case.
+                    let delta = value.wrapping_sub(*last_value) as i64;
+                    write_varint(zigzag_encode(delta), bytes);
+                    *last_value = value;
+                    *count += 1;
+                }
+                Self::Histogram { buckets, count, max } => {
+                    buckets[bucket_index(value)] = buckets[bucket_index(value)].saturating_add(1);
+                    *count += 1;
+                    if value > *max {
+                        *max = value;
+                    }
+                }
+            }
+        }
+
+        fn samples(&self) -> Vec<u64> {
+            match self {
+                Self::Raw { samples, count } => samples[..*count].to_vec(),
+                Self::Compressed { bytes, .. } => CompressedSamples {
+                    bytes,
+                    pos: 0,
+                    last_value: 0,
+                }
+                .collect(),
+                // Individual samples aren't recoverable from bucket counts.
+                Self::Histogram { .. } => Vec::new(),
+            }
+        }
+
+        /// `q` in `[0, 100]`. Exact (via sort) for raw/compressed storage;
+        /// `max` is tracked exactly for histogram storage, everything else
+        /// comes back as its bucket's midpoint.
+        fn percentile(&self, q: f64) -> u64 {
+            match self {
+                Self::Raw { .. } | Self::Compressed { .. } => {
+                    let mut v = self.samples();
+                    if v.is_empty() {
+                        return 0;
+                    }
+                    v.sort_unstable();
+                    percentile_of_sorted(&v, q)
+                }
+                Self::Histogram { buckets, count, max } => {
+                    if *count == 0 {
+                        return 0;
+                    }
+                    if q >= 100.0 {
+                        return *max;
+                    }
+                    let target = ((q / 100.0) * (*count as f64)).ceil().max(1.0) as u64;
+                    let mut cumulative = 0u64;
+                    for (idx, &c) in buckets.iter().enumerate() {
+                        cumulative += c as u64;
+                        if cumulative >= target {
+                            return bucket_midpoint(idx).min(*max);
+                        }
+                    }
+                    *max
+                }
+            }
+        }
+
+        fn max(&self) -> u64 {
+            match self {
+                Self::Histogram { max, .. } => *max,
+                _ => self.samples().into_iter().max().unwrap_or(0),
+            }
+        }
+
+        fn count(&self) -> usize {
+            match self {
+                Self::Raw { count, .. } => *count,
+                Self::Compressed { count, .. } => *count,
+                Self::Histogram { count, .. } => *count as usize,
+            }
+        }
+
+        fn clear(&mut self) {
+            match self {
+                Self::Raw { count, .. } => *count = 0,
+                Self::Compressed {
+                    bytes,
+                    last_value,
+                    count,
+                } => {
+                    bytes.clear();
+                    *last_value = 0;
+                    *count = 0;
+                }
+                Self::Histogram { buckets, count, max } => {
+                    for b in buckets.iter_mut() {
+                        *b = 0;
+                    }
+                    *count = 0;
+                    *max = 0;
+                }
+            }
+        }
+    }
+
+    struct StageBuf {
+        storage: Storage,
+        pending: u64,
+    }
+
+    enum StorageKind {
+        Raw,
+        Compressed,
+        Histogram,
+    }
+
+    impl StageBuf {
+        fn new(kind: &StorageKind) -> Self {
+            Self {
+                storage: match kind {
+                    StorageKind::Raw => Storage::raw(),
+                    StorageKind::Compressed => Storage::compressed(),
+                    StorageKind::Histogram => Storage::histogram(),
+                },
                 pending: 0,
             }
         }
@@ -89,7 +382,26 @@ mod inner {
 
     impl PerfRecorder {
         pub fn new() -> Self {
-            let stages: Vec<StageBuf> = (0..NUM_STAGES).map(|_| StageBuf::new()).collect();
+            Self::new_inner(StorageKind::Raw)
+        }
+
+        /// Like `new()`, but stores samples delta + zigzag + varint encoded
+        /// instead of raw, trading a decode pass in `samples()` for several
+        /// times less memory per stage.
+        pub fn new_compressed() -> Self {
+            Self::new_inner(StorageKind::Compressed)
+        }
+
+        /// Like `new()`, but stores a fixed-memory log-linear histogram per
+        /// stage instead of individual samples — O(1) recording and O(1)
+        /// memory regardless of capture length, at the cost of `samples()`
+        /// coming back empty; use `percentile()`/`max()` instead.
+        pub fn new_histogram() -> Self {
+            Self::new_inner(StorageKind::Histogram)
+        }
+
+        fn new_inner(kind: StorageKind) -> Self {
+            let stages: Vec<StageBuf> = (0..NUM_STAGES).map(|_| StageBuf::new(&kind)).collect();
             Self {
                 stages: stages.into_boxed_slice().try_into().ok().unwrap(),
             }
@@ -104,37 +416,46 @@ mod inner {
         pub fn end(&mut self, stage: PerfStage) {
             let buf = &mut self.stages[stage as usize];
             let elapsed = now_ns().saturating_sub(buf.pending);
-            if buf.count < MAX_SAMPLES {
-                buf.samples[buf.count] = elapsed;
-                buf.count += 1;
-            }
+            buf.storage.push(elapsed);
         }
 
         #[inline(always)]
         pub fn record(&mut self, stage: PerfStage, duration_ns: u64) {
-            let buf = &mut self.stages[stage as usize];
-            if buf.count < MAX_SAMPLES {
-                buf.samples[buf.count] = duration_ns;
-                buf.count += 1;
-            }
+            self.stages[stage as usize].storage.push(duration_ns);
         }
 
-        pub fn samples(&self, stage: PerfStage) -> &[u64] {
-            let buf = &self.stages[stage as usize];
-            &buf.samples[..buf.count]
+        /// Reconstructs this stage's sample stream as an owned `Vec`: raw
+        /// mode copies its stored samples, compressed mode decodes them, and
+        /// histogram mode (which keeps no individual samples) returns an
+        /// empty `Vec`.
+        pub fn samples(&self, stage: PerfStage) -> Vec<u64> {
+            self.stages[stage as usize].storage.samples()
         }
 
         pub fn count(&self, stage: PerfStage) -> usize {
-            self.stages[stage as usize].count
+            self.stages[stage as usize].storage.count()
+        }
+
+        /// Value at percentile `q` (`0.0..=100.0`). Works in every storage
+        /// mode: sorts the decoded stream for raw/compressed, sums buckets
+        /// left-to-right for histogram mode.
+        pub fn percentile(&self, stage: PerfStage, q: f64) -> u64 {
+            self.stages[stage as usize].storage.percentile(q)
+        }
+
+        /// Exact maximum sample, tracked directly in histogram mode since a
+        /// top bucket's midpoint would otherwise understate it.
+        pub fn max(&self, stage: PerfStage) -> u64 {
+            self.stages[stage as usize].storage.max()
         }
 
         pub fn drain(&mut self, stage: PerfStage) {
-            self.stages[stage as usize].count = 0;
+            self.stages[stage as usize].storage.clear();
         }
 
         pub fn reset(&mut self) {
             for buf in self.stages.iter_mut() {
-                buf.count = 0;
+                buf.storage.clear();
             }
         }
     }
@@ -165,20 +486,36 @@ mod inner {
             Self
         }
         #[inline(always)]
+        pub fn new_compressed() -> Self {
+            Self
+        }
+        #[inline(always)]
+        pub fn new_histogram() -> Self {
+            Self
+        }
+        #[inline(always)]
         pub fn begin(&mut self, _stage: PerfStage) {}
         #[inline(always)]
         pub fn end(&mut self, _stage: PerfStage) {}
         #[inline(always)]
         pub fn record(&mut self, _stage: PerfStage, _duration_ns: u64) {}
         #[inline(always)]
-        pub fn samples(&self, _stage: PerfStage) -> &[u64] {
-            &[]
+        pub fn samples(&self, _stage: PerfStage) -> Vec<u64> {
+            Vec::new()
         }
         #[inline(always)]
         pub fn count(&self, _stage: PerfStage) -> usize {
             0
         }
         #[inline(always)]
+        pub fn percentile(&self, _stage: PerfStage, _q: f64) -> u64 {
+            0
+        }
+        #[inline(always)]
+        pub fn max(&self, _stage: PerfStage) -> u64 {
+            0
+        }
+        #[inline(always)]
         pub fn drain(&mut self, _stage: PerfStage) {}
         #[inline(always)]
         pub fn reset(&mut self) {}
@@ -191,4 +528,7 @@ mod inner {
     }
 }
 
+mod shared;
+
 pub use inner::{PerfRecorder, now_ns};
+pub use shared::SharedPerfRecorder;
@@ -0,0 +1,273 @@
+//! Lock-free, multi-producer counterpart to [`crate::PerfRecorder`].
+//!
+//! `PerfRecorder`'s methods all take `&mut self`, so each recording thread
+//! (the Obsidian publisher, the Onyx consumer, ...) needs its own instance.
+//! `SharedPerfRecorder` instead accepts concurrent `record()` calls through
+//! `&self` from any number of threads with no locks on the hot path.
+//!
+//! Each stage is a singly-linked chain of fixed-size blocks. A producer
+//! claims its slot with one atomic fetch-add on the current block's write
+//! index and stores its value with a `Release` store; no other producer
+//! contends for that slot. When a block fills, the producer that discovers
+//! this allocates a fresh block, links the full one behind it, and
+//! `compare_exchange`s it in as the new head — losers of that race simply
+//! retry against whichever block won. `snapshot()` atomically swaps in a
+//! brand new empty head and returns the detached chain, so draining never
+//! has to stop producers. Blocks are freed via `crossbeam_epoch` so a
+//! `snapshot()` can never free a block a concurrent producer is still
+//! writing into; a producer additionally re-checks, after its store, that
+//! the block it wrote into is still reachable from `head` — if a
+//! `snapshot()` detached the whole chain out from under it meanwhile, it
+//! retries the write against the live head instead of trusting a value that
+//! might already have been scanned-and-freed unread.
+
+use crate::{NUM_STAGES, PerfStage};
+
+#[cfg(feature = "record")]
+mod inner {
+    use super::*;
+    use crossbeam_epoch::{self as epoch, Atomic, Owned};
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    const BLOCK_SIZE: usize = 512;
+
+    struct Block {
+        values: [AtomicU64; BLOCK_SIZE],
+        /// Slots claimed so far, via fetch-add; can exceed `BLOCK_SIZE`
+        /// briefly while losers of the "block is full" race back off.
+        len: AtomicUsize,
+        next: Atomic<Block>,
+    }
+
+    impl Block {
+        fn new() -> Self {
+            Self {
+                values: std::array::from_fn(|_| AtomicU64::new(0)),
+                len: AtomicUsize::new(0),
+                next: Atomic::null(),
+            }
+        }
+    }
+
+    struct StageQueue {
+        head: Atomic<Block>,
+    }
+
+    impl StageQueue {
+        fn new() -> Self {
+            Self {
+                head: Atomic::new(Block::new()),
+            }
+        }
+
+        /// True if `target` is still reachable by following `next` from
+        /// `from` — i.e. still part of the live chain rather than one
+        /// `snapshot()` already swapped out from under it. The chain only
+        /// ever grows by linking a fresh block *ahead* of the existing one
+        /// (`fresh.next = old head`), so a block that's still part of it
+        /// stays reachable this way for as long as it's alive; a block a
+        /// `snapshot()` has detached is reachable from nothing `self.head`
+        /// can still reach.
+        fn reachable<'g>(mut from: epoch::Shared<'g, Block>, target: epoch::Shared<'g, Block>, guard: &'g epoch::Guard) -> bool {
+            while !from.is_null() {
+                if from == target {
+                    return true;
+                }
+                // SAFETY: every block reachable from a live head is itself
+                // live for at least the duration of this guard.
+                from = unsafe { from.deref() }.next.load(Ordering::Acquire, guard);
+            }
+            false
+        }
+
+        fn record(&self, value: u64) {
+            loop {
+                let guard = &epoch::pin();
+                let written_into;
+                loop {
+                    let head = self.head.load(Ordering::Acquire, guard);
+                    // SAFETY: `head` is never null and only ever freed by a
+                    // `snapshot()` that has already swapped it out, deferred
+                    // through the epoch guard held for the duration of this call.
+                    let block = unsafe { head.deref() };
+                    let idx = block.len.fetch_add(1, Ordering::AcqRel);
+                    if idx < BLOCK_SIZE {
+                        block.values[idx].store(value, Ordering::Release);
+                        written_into = head;
+                        break;
+                    }
+
+                    // This block is full (or another thread already over-claimed
+                    // it); install a fresh one as the new head, with the
+                    // full block linked behind it via `next`.
+                    let fresh = Owned::new(Block::new()).into_shared(guard);
+                    // SAFETY: `fresh` was just allocated and isn't shared yet.
+                    unsafe { fresh.deref().next.store(head, Ordering::Release) };
+                    if self
+                        .head
+                        .compare_exchange(head, fresh, Ordering::AcqRel, Ordering::Acquire, guard)
+                        .is_err()
+                    {
+                        // Someone else installed a block first; drop ours and
+                        // retry against whichever one won.
+                        // SAFETY: `fresh` was never published, so we're the only owner.
+                        unsafe { drop(fresh.into_owned()) };
+                    }
+                }
+
+                // Check the actual block we wrote into, not a free-floating
+                // counter: another producer extending the chain ahead of us
+                // (installing a fresh block when `written_into` filled up)
+                // leaves `written_into` still reachable via `next`, so that
+                // alone is no cause to retry. Only `snapshot()` detaches the
+                // *whole* chain in one swap, severing it from `self.head`
+                // entirely — that's the only case this needs to catch.
+                let current = self.head.load(Ordering::Acquire, guard);
+                if Self::reachable(current, written_into, guard) {
+                    return;
+                }
+                // `written_into` is gone from the live chain: a snapshot()
+                // raced this call and may have already scanned it before our
+                // store landed, so the value above could be lost. Redo it
+                // against whatever is current now.
+            }
+        }
+
+        /// Swaps in a fresh empty block and returns every value from the
+        /// detached chain, oldest first.
+        fn snapshot(&self) -> Vec<u64> {
+            let guard = &epoch::pin();
+            let fresh = Owned::new(Block::new()).into_shared(guard);
+            let old_head = self
+                .head
+                .swap(fresh, Ordering::AcqRel, guard);
+
+            let mut blocks = Vec::new();
+            let mut cur = old_head;
+            while !cur.is_null() {
+                // SAFETY: every block reachable from `old_head` was live at
+                // the moment of the swap and is only reclaimed below, after
+                // this guard (and therefore any concurrent writer's guard
+                // pinned before the swap) is done with it.
+                let block = unsafe { cur.deref() };
+                let len = block.len.load(Ordering::Acquire).min(BLOCK_SIZE);
+                let mut values = Vec::with_capacity(len);
+                for slot in block.values.iter().take(len) {
+                    values.push(slot.load(Ordering::Acquire));
+                }
+                blocks.push(values);
+
+                let next = block.next.load(Ordering::Acquire, guard);
+                // SAFETY: `cur` has just been unlinked from `head` (or was
+                // already unreachable via `next`) and won't be dereferenced
+                // again after this.
+                unsafe { guard.defer_destroy(cur) };
+                cur = next;
+            }
+
+            // `blocks` runs newest-chain-first; each block's own contents
+            // are already in claim order.
+            blocks.reverse();
+            blocks.into_iter().flatten().collect()
+        }
+    }
+
+    impl Drop for StageQueue {
+        fn drop(&mut self) {
+            let guard = &epoch::pin();
+            let mut cur = self.head.load(Ordering::Acquire, guard);
+            while !cur.is_null() {
+                // SAFETY: `&mut self` means no other reference to this
+                // queue (and therefore no concurrent producer) can exist.
+                let next = unsafe { cur.deref() }.next.load(Ordering::Acquire, guard);
+                unsafe { guard.defer_destroy(cur) };
+                cur = next;
+            }
+        }
+    }
+
+    thread_local! {
+        static PENDING: [Cell<u64>; NUM_STAGES] = [const { Cell::new(0) }; NUM_STAGES];
+    }
+
+    pub struct SharedPerfRecorder {
+        stages: Box<[StageQueue; NUM_STAGES]>,
+    }
+
+    impl SharedPerfRecorder {
+        pub fn new() -> Self {
+            let stages: Vec<StageQueue> = (0..NUM_STAGES).map(|_| StageQueue::new()).collect();
+            Self {
+                stages: stages.into_boxed_slice().try_into().ok().unwrap(),
+            }
+        }
+
+        /// Records the start of `stage` on the calling thread; paired with
+        /// `end()`. Per-thread state, so concurrent callers never interfere.
+        #[inline(always)]
+        pub fn begin(&self, stage: PerfStage) {
+            PENDING.with(|p| p[stage as usize].set(crate::now_ns()));
+        }
+
+        #[inline(always)]
+        pub fn end(&self, stage: PerfStage) {
+            let started = PENDING.with(|p| p[stage as usize].get());
+            self.record(stage, crate::now_ns().saturating_sub(started));
+        }
+
+        #[inline(always)]
+        pub fn record(&self, stage: PerfStage, duration_ns: u64) {
+            self.stages[stage as usize].record(duration_ns);
+        }
+
+        /// Atomically detaches every sample recorded for `stage` so far and
+        /// returns them, oldest first, ready for `compute_stats`. A producer
+        /// racing this call either lands cleanly in the fresh block it
+        /// installs, or notices (by checking reachability from `head` after
+        /// its store) that the block it wrote into was detached by this
+        /// call and retries its write there instead — either way no sample
+        /// is dropped.
+        pub fn snapshot(&self, stage: PerfStage) -> Vec<u64> {
+            self.stages[stage as usize].snapshot()
+        }
+    }
+
+    impl Default for SharedPerfRecorder {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+#[cfg(not(feature = "record"))]
+mod inner {
+    use super::*;
+
+    pub struct SharedPerfRecorder;
+
+    impl SharedPerfRecorder {
+        #[inline(always)]
+        pub fn new() -> Self {
+            Self
+        }
+        #[inline(always)]
+        pub fn begin(&self, _stage: PerfStage) {}
+        #[inline(always)]
+        pub fn end(&self, _stage: PerfStage) {}
+        #[inline(always)]
+        pub fn record(&self, _stage: PerfStage, _duration_ns: u64) {}
+        #[inline(always)]
+        pub fn snapshot(&self, _stage: PerfStage) -> Vec<u64> {
+            Vec::new()
+        }
+    }
+
+    impl Default for SharedPerfRecorder {
+        fn default() -> Self {
+            Self
+        }
+    }
+}
+
+pub use inner::SharedPerfRecorder;
@@ -11,3 +11,13 @@ pub struct BinanceDto<'a> {
     #[serde(rename = "A")]
     pub a_qty: &'a str, // best ask qty
 }
+
+/// Envelope Binance wraps every message in on a combined stream
+/// (`/stream?streams=a@bookTicker/b@bookTicker/...`): the same payload as a
+/// single-stream connection, tagged with which stream it came from.
+#[derive(Debug, Deserialize)]
+pub struct BinanceCombinedStreamDto<'a> {
+    pub stream: &'a str,
+    #[serde(borrow)]
+    pub data: BinanceDto<'a>,
+}
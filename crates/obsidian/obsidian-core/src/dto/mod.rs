@@ -0,0 +1,3 @@
+mod binance;
+
+pub use binance::{BinanceCombinedStreamDto, BinanceDto};
@@ -0,0 +1,4 @@
+pub mod dto;
+pub mod websocket_manager;
+
+pub use websocket_manager::{MAX_SYMBOLS, WebsocketManager, WebsocketSymbolState};
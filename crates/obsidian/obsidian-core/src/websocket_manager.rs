@@ -1,18 +1,131 @@
+use lithos_events::SymbolId;
+use std::collections::HashMap;
+
 pub const MAX_SYMBOLS: usize = 256;
 
-#[derive(Default)]
+/// Per-symbol state tracked for a subscribed stream.
+#[derive(Default, Debug, Clone)]
 pub struct WebsocketSymbolState {
-    pub book_tikcer: String,
+    /// Exchange symbol string as it appears in the combined-stream payload
+    /// (e.g. `"BTCUSDT"`).
+    pub symbol: String,
+    /// Most recent raw `bookTicker` JSON payload received for this symbol.
+    pub last_book_ticker: String,
 }
 
+/// Maps Binance symbol strings to stable `SymbolId`s and tracks per-symbol
+/// state, so a single combined-stream connection can feed every subscribed
+/// symbol's `TopOfBook` through one broadcast ring instead of needing one
+/// process (and one hardcoded `SymbolId`) per symbol.
 pub struct WebsocketManager {
     pub websocket_connections: [WebsocketSymbolState; MAX_SYMBOLS],
+    symbol_to_id: HashMap<String, SymbolId>,
+    next_id: u16,
 }
 
 impl WebsocketManager {
     pub fn new() -> Self {
         Self {
             websocket_connections: std::array::from_fn(|_| WebsocketSymbolState::default()),
+            symbol_to_id: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Registers `symbol`, assigning it a fresh `SymbolId` if not already known.
+    ///
+    /// # Panics
+    /// Panics if registering `symbol` would exceed `MAX_SYMBOLS` distinct symbols.
+    pub fn register(&mut self, symbol: &str) -> SymbolId {
+        if let Some(&id) = self.symbol_to_id.get(symbol) {
+            return id;
         }
+
+        let idx = self.next_id as usize;
+        assert!(
+            idx < MAX_SYMBOLS,
+            "cannot register symbol '{symbol}': exceeded MAX_SYMBOLS ({MAX_SYMBOLS})"
+        );
+
+        let id = SymbolId(self.next_id);
+        self.next_id += 1;
+        self.symbol_to_id.insert(symbol.to_string(), id);
+        self.websocket_connections[idx].symbol = symbol.to_string();
+        id
+    }
+
+    /// Looks up the `SymbolId` for an already-registered symbol.
+    pub fn symbol_id(&self, symbol: &str) -> Option<SymbolId> {
+        self.symbol_to_id.get(symbol).copied()
+    }
+
+    /// Records the latest raw `bookTicker` payload seen for `symbol_id`.
+    pub fn record_book_ticker(&mut self, symbol_id: SymbolId, raw: &str) {
+        if let Some(state) = self.websocket_connections.get_mut(symbol_id.0 as usize) {
+            state.last_book_ticker.clear();
+            state.last_book_ticker.push_str(raw);
+        }
+    }
+
+    /// Builds the path for a Binance combined stream subscribing to every
+    /// symbol in `symbols` as a `bookTicker` feed, registering each one in
+    /// the process: `/stream?streams=btcusdt@bookTicker/ethusdt@bookTicker`.
+    ///
+    /// # Panics
+    /// Panics if `symbols.len()` exceeds `MAX_SYMBOLS`.
+    pub fn combined_stream_path(&mut self, symbols: &[&str]) -> String {
+        assert!(
+            symbols.len() <= MAX_SYMBOLS,
+            "cannot subscribe to more than MAX_SYMBOLS ({MAX_SYMBOLS}) symbols at once"
+        );
+
+        let streams: Vec<String> = symbols
+            .iter()
+            .map(|symbol| {
+                self.register(&symbol.to_uppercase());
+                format!("{}@bookTicker", symbol.to_lowercase())
+            })
+            .collect();
+
+        format!("/stream?streams={}", streams.join("/"))
+    }
+}
+
+impl Default for WebsocketManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_assigns_stable_ids_and_dedupes() {
+        let mut mgr = WebsocketManager::new();
+        let btc = mgr.register("BTCUSDT");
+        let eth = mgr.register("ETHUSDT");
+        assert_ne!(btc, eth);
+        assert_eq!(mgr.register("BTCUSDT"), btc);
+        assert_eq!(mgr.symbol_id("ETHUSDT"), Some(eth));
+        assert_eq!(mgr.websocket_connections[btc.0 as usize].symbol, "BTCUSDT");
+    }
+
+    #[test]
+    fn combined_stream_path_builds_expected_format() {
+        let mut mgr = WebsocketManager::new();
+        let path = mgr.combined_stream_path(&["BTCUSDT", "ethusdt"]);
+        assert_eq!(path, "/stream?streams=btcusdt@bookTicker/ethusdt@bookTicker");
+        assert!(mgr.symbol_id("BTCUSDT").is_some());
+        assert!(mgr.symbol_id("ETHUSDT").is_some());
+    }
+
+    #[test]
+    fn record_book_ticker_updates_symbol_state() {
+        let mut mgr = WebsocketManager::new();
+        let id = mgr.register("BTCUSDT");
+        mgr.record_book_ticker(id, r#"{"s":"BTCUSDT"}"#);
+        assert_eq!(mgr.websocket_connections[id.0 as usize].last_book_ticker, r#"{"s":"BTCUSDT"}"#);
     }
 }
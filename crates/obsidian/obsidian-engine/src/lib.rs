@@ -1,14 +1,16 @@
 use lithos_events::{SymbolId, TopOfBook};
 use lithos_icc::BroadcastWriter;
 use obsidian_config::config::ConnectionConfig;
-use obsidian_core::dto::BinanceDto;
+use obsidian_core::dto::{BinanceCombinedStreamDto, BinanceDto};
+use obsidian_core::WebsocketManager;
 use obsidian_util::binance_book_ticker::parse_binance_book_ticker_fast;
-use obsidian_util::floating_parse::{parse_px_2dp, parse_qty_3dp};
+use obsidian_util::floating_parse::{ParseFixedError, try_parse_px_2dp, try_parse_qty_3dp};
 use obsidian_util::timestamp::now_ns;
 use sonic_rs::from_slice;
 use std::io;
 use std::net::TcpStream;
 use std::path::Path;
+use std::time::Duration;
 #[cfg(debug_assertions)]
 use tracing::debug;
 use tracing::warn;
@@ -24,6 +26,10 @@ pub type WebsocketStream = WebSocket<MaybeTlsStream<TcpStream>>;
 pub struct ObsidianProcessor {
     pub writer: BroadcastWriter<TopOfBook>,
     pub symbol_id: SymbolId,
+    /// Count of messages dropped due to malformed JSON or numeric fields.
+    /// Incremented instead of panicking so one bad exchange message can't
+    /// tear down the whole publisher.
+    pub parse_errors: u64,
     #[cfg(feature = "perf")]
     pub perf: PerfRecorder,
 }
@@ -34,6 +40,7 @@ impl ObsidianProcessor {
         Ok(Self {
             writer,
             symbol_id,
+            parse_errors: 0,
             #[cfg(feature = "perf")]
             perf: PerfRecorder::new(),
         })
@@ -57,7 +64,8 @@ impl ObsidianProcessor {
             match dto {
                 Ok(dto) => Some((dto.b, dto.b_qty, dto.a, dto.a_qty)),
                 Err(e) => {
-                    warn!(?e, "unable to parse websocket payload");
+                    warn!(?e, "unable to parse websocket payload; skipping message");
+                    self.parse_errors += 1;
                     #[cfg(feature = "perf")]
                     self.perf.end(PerfStage::ParseJson);
                     #[cfg(feature = "perf")]
@@ -76,14 +84,29 @@ impl ObsidianProcessor {
         #[cfg(feature = "perf")]
         self.perf.begin(PerfStage::ParseNumeric);
 
-        let bid_px = parse_px_2dp(b);
-        let bid_qty = parse_qty_3dp(b_qty);
-        let ask_px = parse_px_2dp(a);
-        let ask_qty = parse_qty_3dp(a_qty);
+        let numeric = (|| {
+            Ok::<_, ParseFixedError>((
+                try_parse_px_2dp(b)?,
+                try_parse_qty_3dp(b_qty)?,
+                try_parse_px_2dp(a)?,
+                try_parse_qty_3dp(a_qty)?,
+            ))
+        })();
 
         #[cfg(feature = "perf")]
         self.perf.end(PerfStage::ParseNumeric);
 
+        let (bid_px, bid_qty, ask_px, ask_qty) = match numeric {
+            Ok(fields) => fields,
+            Err(e) => {
+                warn!(?e, "unable to parse numeric field; skipping message");
+                self.parse_errors += 1;
+                #[cfg(feature = "perf")]
+                self.perf.end(PerfStage::ObsidianTotal);
+                return false;
+            }
+        };
+
         // ── Timestamp ──
         #[cfg(feature = "perf")]
         self.perf.begin(PerfStage::TimestampEvent);
@@ -120,8 +143,12 @@ impl ObsidianProcessor {
 
         #[cfg(debug_assertions)]
         {
+            // `next_correlation_id()` is allocated after `publish()` purely
+            // for log correlation here; it has no bearing on `tob`'s own
+            // sequence number in the ring.
             let symbol_id = tob.symbol_id.0;
-            debug!("market_state[{}]: {:?}", symbol_id, tob);
+            let corr_id = self.writer.next_correlation_id();
+            debug!("market_state[{}] corr={}: {:?}", symbol_id, corr_id, tob);
         }
 
         #[cfg(feature = "perf")]
@@ -175,3 +202,159 @@ impl ObsidianEngine {
         }
     }
 }
+
+/// Multi-symbol engine: subscribes to a Binance combined stream and maps
+/// each message's `s` field to a `SymbolId` via a [`WebsocketManager`]
+/// registry, instead of the single hardcoded `SymbolId` `ObsidianEngine`
+/// uses. One process can therefore feed the whole onyx state manager
+/// regardless of how many symbols it tracks (up to `MAX_SYMBOLS`).
+pub struct ObsidianMultiEngine {
+    pub writer: BroadcastWriter<TopOfBook>,
+    pub manager: WebsocketManager,
+    pub socket: WebsocketStream,
+    /// Count of messages dropped due to malformed JSON, malformed numeric
+    /// fields, or an unregistered symbol.
+    pub parse_errors: u64,
+    base_url: String,
+    stream_path: String,
+}
+
+impl ObsidianMultiEngine {
+    /// Opens the shared ring at `path` and connects to `base_url` subscribed
+    /// to a combined `bookTicker` stream for every symbol in `symbols`
+    /// (e.g. `["BTCUSDT", "ETHUSDT"]`).
+    pub fn new<P: AsRef<Path>>(path: P, base_url: &str, symbols: &[&str]) -> io::Result<Self> {
+        let writer = BroadcastWriter::<TopOfBook>::open(path)?;
+        let mut manager = WebsocketManager::new();
+        let stream_path = manager.combined_stream_path(symbols);
+        let socket = Self::connect(base_url, &stream_path)?;
+
+        Ok(Self {
+            writer,
+            manager,
+            socket,
+            parse_errors: 0,
+            base_url: base_url.to_string(),
+            stream_path,
+        })
+    }
+
+    fn connect(base_url: &str, stream_path: &str) -> io::Result<WebsocketStream> {
+        let url = format!("{base_url}{stream_path}");
+        let (mut socket, _response) =
+            connect(&url).map_err(|e| io::Error::other(format!("connect failed: {e}")))?;
+        if let Err(e) = socket.get_mut().set_nodelay(true) {
+            warn!(?e, "failed to set TCP_NODELAY");
+        }
+        Ok(socket)
+    }
+
+    /// Reconnects with exponential backoff (capped at 30s), doubling the
+    /// delay after every failed attempt, so a dropped connection doesn't
+    /// tear down the publisher.
+    fn reconnect_with_backoff(&mut self) {
+        const MAX_BACKOFF: Duration = Duration::from_secs(30);
+        let mut backoff = Duration::from_millis(500);
+
+        loop {
+            match Self::connect(&self.base_url, &self.stream_path) {
+                Ok(socket) => {
+                    self.socket = socket;
+                    return;
+                }
+                Err(e) => {
+                    warn!(
+                        ?e,
+                        delay_ms = backoff.as_millis() as u64,
+                        "reconnect failed; backing off"
+                    );
+                    std::thread::sleep(backoff);
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Parses one combined-stream text message, maps its symbol to a
+    /// `SymbolId`, and publishes a `TopOfBook`. Returns `true` if published,
+    /// `false` on parse failure or an unregistered symbol.
+    fn process_text(&mut self, text: &str) -> bool {
+        let envelope: Result<BinanceCombinedStreamDto, _> = from_slice(text.as_bytes());
+        let dto = match envelope {
+            Ok(envelope) => envelope.data,
+            Err(e) => {
+                warn!(?e, "unable to parse combined-stream payload; skipping message");
+                self.parse_errors += 1;
+                return false;
+            }
+        };
+
+        let Some(symbol_id) = self.manager.symbol_id(dto.s) else {
+            warn!(symbol = dto.s, "message for unregistered symbol; skipping");
+            self.parse_errors += 1;
+            return false;
+        };
+
+        let numeric = (|| {
+            Ok::<_, ParseFixedError>((
+                try_parse_px_2dp(dto.b)?,
+                try_parse_qty_3dp(dto.b_qty)?,
+                try_parse_px_2dp(dto.a)?,
+                try_parse_qty_3dp(dto.a_qty)?,
+            ))
+        })();
+
+        let (bid_px, bid_qty, ask_px, ask_qty) = match numeric {
+            Ok(fields) => fields,
+            Err(e) => {
+                warn!(?e, "unable to parse numeric field; skipping message");
+                self.parse_errors += 1;
+                return false;
+            }
+        };
+
+        self.manager.record_book_ticker(symbol_id, text);
+
+        let tob = TopOfBook {
+            ts_event_ns: now_ns(),
+            symbol_id,
+            bid_px_ticks: bid_px,
+            bid_qty_lots: bid_qty,
+            ask_px_ticks: ask_px,
+            ask_qty_lots: ask_qty,
+        };
+        self.writer.publish(tob);
+
+        true
+    }
+
+    /// Runs the ingestion loop forever, reconnecting with backoff on socket
+    /// errors or a server-initiated close instead of tearing down the
+    /// publisher.
+    pub fn run(&mut self) {
+        loop {
+            let data = match self.socket.read() {
+                Ok(data) => data,
+                Err(e) => {
+                    warn!(?e, "socket read failed; reconnecting");
+                    self.reconnect_with_backoff();
+                    continue;
+                }
+            };
+
+            match data {
+                Message::Text(text) => {
+                    self.process_text(text.as_ref());
+                }
+                Message::Ping(payload) => {
+                    self.socket.write(Message::Pong(payload)).ok();
+                }
+                Message::Close(_) => {
+                    warn!("server closed connection; reconnecting");
+                    self.reconnect_with_backoff();
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -12,6 +12,123 @@ pub fn parse_qty_3dp(s: &str) -> i64 {
     parse_fixed_dp::<3>(s)
 }
 
+/// Why a fallible parse of a fixed-point decimal string failed.
+///
+/// Distinguishes the ways a hostile or malformed feed can break
+/// [`parse_fixed_dp`]'s assumptions so a caller can log/metric each failure
+/// mode separately instead of lumping everything into "parse failed".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ParseFixedError {
+    #[error("empty input")]
+    Empty,
+    #[error("multiple sign characters")]
+    MultipleSigns,
+    #[error("empty integer part")]
+    EmptyIntegerPart,
+    #[error("empty fractional part")]
+    EmptyFractionalPart,
+    #[error("non-digit byte {byte:#04x} at offset {offset}")]
+    NonDigit { offset: usize, byte: u8 },
+    #[error("integer part overflows i64")]
+    Overflow,
+}
+
+/// Fallible counterpart to [`parse_fixed_dp`].
+///
+/// Validates every byte is an ASCII digit (aside from a single leading
+/// sign and the decimal point), rejects empty integer/fractional parts,
+/// and uses checked arithmetic so a hostile/huge feed returns
+/// [`ParseFixedError::Overflow`] instead of silently wrapping. Prefer this
+/// over `parse_fixed_dp` whenever the input isn't already known-good (e.g.
+/// live ingestion), and keep the infallible version for the benchmarked
+/// hot path where the upstream exchange payload is trusted.
+pub fn try_parse_fixed_dp<const DP: u32>(s: &str) -> Result<i64, ParseFixedError> {
+    let b = s.as_bytes();
+    let len = b.len();
+    if len == 0 {
+        return Err(ParseFixedError::Empty);
+    }
+
+    let mut start = 0usize;
+    let mut sign = 1i64;
+    if b[start] == b'-' || b[start] == b'+' {
+        sign = if b[start] == b'-' { -1 } else { 1 };
+        start += 1;
+    }
+    if start < len && (b[start] == b'-' || b[start] == b'+') {
+        return Err(ParseFixedError::MultipleSigns);
+    }
+
+    let dot_idx = s[start..]
+        .bytes()
+        .position(|c| c == b'.')
+        .map(|p| start + p)
+        .unwrap_or(len);
+
+    if dot_idx == start {
+        return Err(ParseFixedError::EmptyIntegerPart);
+    }
+
+    let mut int_part: i64 = 0;
+    for offset in start..dot_idx {
+        let c = b[offset];
+        if !c.is_ascii_digit() {
+            return Err(ParseFixedError::NonDigit { offset, byte: c });
+        }
+        int_part = int_part
+            .checked_mul(10)
+            .and_then(|v| v.checked_add((c - b'0') as i64))
+            .ok_or(ParseFixedError::Overflow)?;
+    }
+
+    let frac_start = if dot_idx < len { dot_idx + 1 } else { len };
+    if dot_idx < len && frac_start >= len {
+        return Err(ParseFixedError::EmptyFractionalPart);
+    }
+
+    let frac_end = (frac_start + DP as usize).min(len);
+    let mut frac: i64 = 0;
+    let mut got = 0u32;
+    for offset in frac_start..frac_end {
+        let c = b[offset];
+        if !c.is_ascii_digit() {
+            return Err(ParseFixedError::NonDigit { offset, byte: c });
+        }
+        frac = frac * 10 + (c - b'0') as i64;
+        got += 1;
+    }
+    // Digits past `DP` are truncated, but still have to be digits.
+    for offset in frac_end..len {
+        let c = b[offset];
+        if !c.is_ascii_digit() {
+            return Err(ParseFixedError::NonDigit { offset, byte: c });
+        }
+    }
+    while got < DP {
+        frac *= 10;
+        got += 1;
+    }
+
+    let scaled = int_part
+        .checked_mul(pow10::<DP>())
+        .and_then(|v| v.checked_add(frac))
+        .ok_or(ParseFixedError::Overflow)?;
+
+    Ok(sign * scaled)
+}
+
+/// Fallible price parse (2 decimal places). See [`try_parse_fixed_dp`].
+#[inline]
+pub fn try_parse_px_2dp(s: &str) -> Result<i64, ParseFixedError> {
+    try_parse_fixed_dp::<2>(s)
+}
+
+/// Fallible quantity parse (3 decimal places). See [`try_parse_fixed_dp`].
+#[inline]
+pub fn try_parse_qty_3dp(s: &str) -> Result<i64, ParseFixedError> {
+    try_parse_fixed_dp::<3>(s)
+}
+
 /// Computes 10^DP at compile time for efficient fixed-point arithmetic.
 /// This avoids runtime exponentiation and allows the compiler to optimize.
 /// Examples: pow10::<2>() = 100, pow10::<3>() = 1000
@@ -30,12 +147,75 @@ fn pow10<const DP: u32>() -> i64 {
     }
 }
 
+/// Combines up to 8 ASCII digit bytes (loaded little-endian into a `u64`) into
+/// their integer value using the SWAR (SIMD-within-a-register) digit-folding
+/// trick, replacing 8 dependent per-digit multiply/adds with 3 independent
+/// shift/multiply stages.
+///
+/// `chunk` must hold ASCII digits `'0'..='9'` in each of its 8 bytes (as
+/// produced by `u64::from_le_bytes`); the byte at index 0 (the *first*
+/// character in the source string) becomes the least-significant byte of the
+/// little-endian word, which is exactly what makes `v >> 8/16/32` walk from
+/// least- to most-significant digit pairs below.
+///
+/// Stage by stage:
+/// 1. Subtract `0x30` from every byte to turn ASCII digits into 0-9 nibbles.
+/// 2. Fold adjacent digit pairs into 2-digit values: `d0*10 + d1`.
+/// 3. Fold adjacent pairs into 4-digit values: `dd0*100 + dd1`.
+/// 4. Fold the two 4-digit halves into the final 8-digit value.
+#[inline(always)]
+fn combine_swar(chunk: u64) -> u64 {
+    let mut v = chunk.wrapping_sub(0x3030_3030_3030_3030);
+    v = (v.wrapping_mul(10) + (v >> 8)) & 0x00FF_00FF_00FF_00FF;
+    v = (v.wrapping_mul(100) + (v >> 16)) & 0x0000_FFFF_0000_FFFF;
+    v = (v.wrapping_mul(10_000) + (v >> 32)) & 0x0000_0000_FFFF_FFFF;
+    v
+}
+
+/// Parses a run of up to 8 ASCII digits into its integer value.
+///
+/// Segments of 8 digits or fewer are parsed branch-free via [`combine_swar`]:
+/// the digits are loaded into a zero-padded 8-byte buffer (padding on the
+/// *right*, i.e. the low-order end once swapped into the SWAR word) and the
+/// resulting value is scaled back down by the padding factor. Longer segments
+/// (not expected for price/qty fields, but possible on hostile input) fall
+/// back to the original per-digit scalar loop.
+#[inline(always)]
+fn parse_digits_fast(b: &[u8]) -> i64 {
+    let len = b.len();
+    if len == 0 {
+        return 0;
+    }
+    if len <= 8 {
+        let mut buf = [b'0'; 8];
+        buf[..len].copy_from_slice(b);
+        let chunk = u64::from_le_bytes(buf);
+        let v = combine_swar(chunk);
+        (v / scale_for_padding(8 - len)) as i64
+    } else {
+        let mut acc = 0i64;
+        for &c in b {
+            acc = acc * 10 + (c - b'0') as i64;
+        }
+        acc
+    }
+}
+
+/// Returns `10^n`, used to undo the zero-padding applied for segments
+/// shorter than 8 digits in [`parse_digits_fast`].
+#[inline(always)]
+fn scale_for_padding(n: usize) -> u64 {
+    const POW10: [u64; 9] = [1, 10, 100, 1_000, 10_000, 100_000, 1_000_000, 10_000_000, 100_000_000];
+    POW10[n]
+}
+
 /// Parses a decimal string into a fixed-point integer representation.
 ///
 /// Algorithm overview:
 /// 1. Handle optional negative sign
-/// 2. Parse integer part (digits before decimal point)
-/// 3. Parse fractional part (digits after decimal point, up to DP digits)
+/// 2. Parse integer part (digits before decimal point) via SWAR batch folding
+/// 3. Parse fractional part (digits after decimal point, up to DP digits) via
+///    the same batch folding
 /// 4. Pad fractional part with zeros if fewer than DP digits were provided
 /// 5. Combine: sign * (int_part * 10^DP + frac_part)
 ///
@@ -75,33 +255,28 @@ pub fn parse_fixed_dp<const DP: u32>(s: &str) -> i64 {
         }
     };
 
-    let mut int_part = 0i64;
-    let mut i = start;
-    while i < dot_idx {
-        let c = unsafe { *b.get_unchecked(i) };
-        int_part = int_part * 10 + (c - b'0') as i64;
-        i += 1;
-    }
+    let int_part = parse_digits_fast(&b[start..dot_idx]);
 
     let frac_start = if dot_idx < len { dot_idx + 1 } else { len };
     let frac_end = (frac_start + DP as usize).min(len);
 
-    let mut frac = 0i64;
-    let mut got = 0u32;
-    i = frac_start;
-    while i < frac_end {
-        let c = unsafe { *b.get_unchecked(i) };
+    // Trim to the longest valid leading run of digits; the SWAR fold assumes
+    // every byte it loads is a digit, so a non-digit byte would corrupt it.
+    let mut valid_end = frac_start;
+    while valid_end < frac_end {
+        let c = unsafe { *b.get_unchecked(valid_end) };
         if c < b'0' || c > b'9' {
             break;
         }
-        frac = frac * 10 + (c - b'0') as i64;
-        got += 1;
-        i += 1;
+        valid_end += 1;
     }
 
-    while got < DP {
+    let got = (valid_end - frac_start) as u32;
+    let mut frac = parse_digits_fast(&b[frac_start..valid_end]);
+    let mut pad = got;
+    while pad < DP {
         frac *= 10;
-        got += 1;
+        pad += 1;
     }
 
     sign * (int_part * pow10::<DP>() + frac)
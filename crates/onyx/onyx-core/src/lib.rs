@@ -0,0 +1,7 @@
+pub mod market_state;
+pub mod market_state_manager;
+pub mod tob_journal;
+
+pub use market_state::MarketsState;
+pub use market_state_manager::MarketStateManager;
+pub use tob_journal::{TobJournal, TobJournalReplayer};
@@ -10,6 +10,7 @@
 // Strust values need to be public since we will use functions to update the state
 
 use lithos_events::{SymbolId, TopOfBook};
+use std::io::{self, Write};
 
 #[derive(Debug)]
 pub struct MarketsState {
@@ -36,4 +37,58 @@ pub struct MarketsState {
     /// Spread (in ticks) , ask_price - bid_price
     /// Value is always > 0 for a valid orderbook since ask_price is greater than bid_price
     pub spread_ticks: i64,
+}
+
+impl MarketsState {
+    /// Fixed-width snapshot record: `symbol_id(2) + last_tob{ts_event_ns(8),
+    /// symbol_id(2), bid_px_ticks(8), bid_qty_lots(8), ask_px_ticks(8),
+    /// ask_qty_lots(8)} + mid_x2(8) + spread_ticks(8) + last_update_ns(8)`.
+    /// Flat and fixed-width on purpose — decode is a straight field-by-field
+    /// read with no branching, matching the manager's own hot-path
+    /// philosophy (see `market_state_manager.rs`).
+    pub const RECORD_LEN: usize = 2 + (8 + 2 + 8 + 8 + 8 + 8) + 8 + 8 + 8;
+
+    pub fn write_record(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&self.symbol_id.0.to_le_bytes())?;
+        w.write_all(&self.last_tob.ts_event_ns.to_le_bytes())?;
+        w.write_all(&self.last_tob.symbol_id.0.to_le_bytes())?;
+        w.write_all(&self.last_tob.bid_px_ticks.to_le_bytes())?;
+        w.write_all(&self.last_tob.bid_qty_lots.to_le_bytes())?;
+        w.write_all(&self.last_tob.ask_px_ticks.to_le_bytes())?;
+        w.write_all(&self.last_tob.ask_qty_lots.to_le_bytes())?;
+        w.write_all(&self.mid_x2.to_le_bytes())?;
+        w.write_all(&self.spread_ticks.to_le_bytes())?;
+        w.write_all(&self.last_update_ns.to_le_bytes())
+    }
+
+    pub fn read_record(buf: &[u8]) -> Option<Self> {
+        if buf.len() < Self::RECORD_LEN {
+            return None;
+        }
+        let symbol_id = SymbolId(u16::from_le_bytes(buf[0..2].try_into().ok()?));
+        let ts_event_ns = u64::from_le_bytes(buf[2..10].try_into().ok()?);
+        let tob_symbol_id = SymbolId(u16::from_le_bytes(buf[10..12].try_into().ok()?));
+        let bid_px_ticks = i64::from_le_bytes(buf[12..20].try_into().ok()?);
+        let bid_qty_lots = i64::from_le_bytes(buf[20..28].try_into().ok()?);
+        let ask_px_ticks = i64::from_le_bytes(buf[28..36].try_into().ok()?);
+        let ask_qty_lots = i64::from_le_bytes(buf[36..44].try_into().ok()?);
+        let mid_x2 = i64::from_le_bytes(buf[44..52].try_into().ok()?);
+        let spread_ticks = i64::from_le_bytes(buf[52..60].try_into().ok()?);
+        let last_update_ns = u64::from_le_bytes(buf[60..68].try_into().ok()?);
+
+        Some(Self {
+            symbol_id,
+            last_tob: TopOfBook {
+                ts_event_ns,
+                symbol_id: tob_symbol_id,
+                bid_px_ticks,
+                bid_qty_lots,
+                ask_px_ticks,
+                ask_qty_lots,
+            },
+            last_update_ns,
+            mid_x2,
+            spread_ticks,
+        })
+    }
 }
\ No newline at end of file
@@ -100,12 +100,22 @@
 //  Vec<T> indexed            | <1 ns   | Great   | No      | Minimal
 //  Box<[T; N]>               | <1 ns   | Great   | No      | Minimal
 
+use std::io::{self, Read, Write};
+
 use lithos_events::TopOfBook;
 
 use crate::market_state::MarketsState;
 
-///  Max symbols we will track
-pub const MAX_SYMBOLS: usize = 256;
+///  Max symbols we will track — the full `SymbolId` (`u16`) range, per the
+///  design rationale above: the array is sized so every possible
+///  `symbol_id.0 as usize` is always a valid index, which is what lets
+///  `update_market_state_tob` index it directly with no bounds check.
+pub const MAX_SYMBOLS: usize = u16::MAX as usize + 1;
+
+/// Identifies a `MarketStateManager` snapshot file; distinguishes it from
+/// any other binary blob a caller might hand to `read_snapshot`.
+const SNAPSHOT_MAGIC: u32 = 0x4D53_4E50; // "MSNP"
+const SNAPSHOT_VERSION: u32 = 1;
 
 pub struct MarketStateManager {
     markets: [MarketsState; MAX_SYMBOLS],
@@ -132,9 +142,67 @@ impl MarketStateManager {
         let tob_symbol = tob.symbol_id.0 as usize;
 
         // gets mutable ref to the market state at that index
-        // using unsafe to prevent implicit check on bound and prevent branching
+        // using unsafe to prevent implicit check on bound and prevent branching:
+        // sound because `markets` is sized to MAX_SYMBOLS == u16::MAX + 1, so
+        // every value a u16 `symbol_id` can hold is always a valid index.
         let market = &mut unsafe { self.markets.get_unchecked_mut(tob_symbol) };
 
         market.update_state_tob(&tob);
     }
+
+    /// Writes every slot that has seen at least one TOB update (`last_update_ns
+    /// != 0`) as a framed binary snapshot: a header (magic, version,
+    /// `MAX_SYMBOLS`, active-slot count) followed by one length-prefixed
+    /// `MarketsState::RECORD_LEN` record per active slot.
+    pub fn write_snapshot(&self, w: &mut impl Write) -> io::Result<()> {
+        let active: Vec<&MarketsState> = self
+            .markets
+            .iter()
+            .filter(|m| m.last_update_ns != 0)
+            .collect();
+
+        w.write_all(&SNAPSHOT_MAGIC.to_le_bytes())?;
+        w.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+        w.write_all(&(MAX_SYMBOLS as u64).to_le_bytes())?;
+        w.write_all(&(active.len() as u64).to_le_bytes())?;
+
+        for market in active {
+            w.write_all(&(MarketsState::RECORD_LEN as u32).to_le_bytes())?;
+            market.write_record(w)?;
+        }
+        Ok(())
+    }
+
+    /// Rehydrates a snapshot written by `write_snapshot`, overwriting the
+    /// slots present in it. Slots the snapshot didn't cover (inactive at
+    /// capture time) are left at whatever state `self` already had.
+    pub fn read_snapshot(&mut self, r: &mut impl Read) -> io::Result<()> {
+        let mut header = [0u8; 4 + 4 + 8 + 8];
+        r.read_exact(&mut header)?;
+        let magic = u32::from_le_bytes(header[0..4].try_into().unwrap());
+        if magic != SNAPSHOT_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "bad MarketStateManager snapshot magic",
+            ));
+        }
+        let active_count = u64::from_le_bytes(header[16..24].try_into().unwrap());
+
+        for _ in 0..active_count {
+            let mut len_buf = [0u8; 4];
+            r.read_exact(&mut len_buf)?;
+            let len = u32::from_le_bytes(len_buf) as usize;
+            let mut buf = vec![0u8; len];
+            r.read_exact(&mut buf)?;
+
+            let market = MarketsState::read_record(&buf).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "malformed snapshot record")
+            })?;
+            let idx = market.symbol_id.0 as usize;
+            if idx < MAX_SYMBOLS {
+                self.markets[idx] = market;
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,198 @@
+//! Append-only, segmented journal of incoming `TopOfBook` events.
+//!
+//! Unlike `MarketStateManager::write_snapshot` (a point-in-time dump of
+//! current state), this captures the full event stream so a run can be
+//! replayed from scratch — deterministic testing, post-mortem analysis, or
+//! warm-starting the engine by replaying history before switching over to
+//! the live bus.
+//!
+//! Records are fixed-width LE (`seq: u64` + the same fields as
+//! `TopOfBook`), written across rotating segment files so a long capture
+//! doesn't grow one file without bound.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use lithos_events::{SymbolId, TopOfBook};
+
+use crate::market_state_manager::{MarketStateManager, MAX_SYMBOLS};
+
+/// `seq(8) + ts_event_ns(8) + symbol_id(2) + bid_px_ticks(8) + bid_qty_lots(8)
+/// + ask_px_ticks(8) + ask_qty_lots(8)`.
+const RECORD_LEN: usize = 8 + 8 + 2 + 8 + 8 + 8 + 8;
+
+/// Records per segment file before rolling to a new one.
+const DEFAULT_SEGMENT_RECORDS: u64 = 1_000_000;
+
+/// Appends incoming `TopOfBook` events to a directory of rotating segment
+/// files, each record carrying a monotonic sequence number.
+pub struct TobJournal {
+    dir: PathBuf,
+    segment_records: u64,
+    writer: BufWriter<File>,
+    current_segment: u64,
+    records_in_segment: u64,
+    next_seq: u64,
+}
+
+impl TobJournal {
+    /// Creates (or resumes appending into) a journal directory, rolling
+    /// every `DEFAULT_SEGMENT_RECORDS` records.
+    pub fn create(dir: impl AsRef<Path>) -> io::Result<Self> {
+        Self::create_with_segment_size(dir, DEFAULT_SEGMENT_RECORDS)
+    }
+
+    pub fn create_with_segment_size(dir: impl AsRef<Path>, segment_records: u64) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        let writer = BufWriter::new(Self::open_segment(&dir, 0)?);
+        Ok(Self {
+            dir,
+            segment_records,
+            writer,
+            current_segment: 0,
+            records_in_segment: 0,
+            next_seq: 0,
+        })
+    }
+
+    fn open_segment(dir: &Path, idx: u64) -> io::Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(format!("segment_{idx:020}.tobj")))
+    }
+
+    /// Appends one record, rolling to a fresh segment file first if the
+    /// current one has hit its record limit.
+    pub fn append(&mut self, tob: &TopOfBook) -> io::Result<()> {
+        if self.records_in_segment >= self.segment_records {
+            self.current_segment += 1;
+            self.writer = BufWriter::new(Self::open_segment(&self.dir, self.current_segment)?);
+            self.records_in_segment = 0;
+        }
+
+        self.writer.write_all(&self.next_seq.to_le_bytes())?;
+        self.writer.write_all(&tob.ts_event_ns.to_le_bytes())?;
+        self.writer.write_all(&tob.symbol_id.0.to_le_bytes())?;
+        self.writer.write_all(&tob.bid_px_ticks.to_le_bytes())?;
+        self.writer.write_all(&tob.bid_qty_lots.to_le_bytes())?;
+        self.writer.write_all(&tob.ask_px_ticks.to_le_bytes())?;
+        self.writer.write_all(&tob.ask_qty_lots.to_le_bytes())?;
+
+        self.next_seq += 1;
+        self.records_in_segment += 1;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Reads every segment in a `TobJournal`'s directory back in order and
+/// feeds each record through `MarketStateManager::update_market_state_tob`,
+/// at full replay speed (no timing reproduction, unlike
+/// `lithos_icc::Journal`'s `ReplaySpeed::Realtime`).
+pub struct TobJournalReplayer {
+    segments: Vec<PathBuf>,
+    next_expected_seq: u64,
+    /// Count of sequence-number gaps seen so far — records a crash or a
+    /// torn write dropped.
+    gaps: u64,
+    /// Count of records skipped because `symbol_id` was out of
+    /// `MAX_SYMBOLS` range — a corrupted file, disk bit-rot, or a journal
+    /// captured against a feed with more symbols than this build supports.
+    bad_symbols: u64,
+}
+
+impl TobJournalReplayer {
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut segments: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "tobj"))
+            .collect();
+        segments.sort();
+        Ok(Self {
+            segments,
+            next_expected_seq: 0,
+            gaps: 0,
+            bad_symbols: 0,
+        })
+    }
+
+    pub fn gaps(&self) -> u64 {
+        self.gaps
+    }
+
+    pub fn bad_symbols(&self) -> u64 {
+        self.bad_symbols
+    }
+
+    /// Replays every record across all segments, in order, into `manager`.
+    pub fn replay(&mut self, manager: &mut MarketStateManager) -> io::Result<()> {
+        let mut buf = vec![0u8; RECORD_LEN];
+        for segment in &self.segments {
+            let mut file = File::open(segment)?;
+            loop {
+                if !read_record_or_eof(&mut file, &mut buf)? {
+                    break;
+                }
+
+                let seq = u64::from_le_bytes(buf[0..8].try_into().unwrap());
+                if seq != self.next_expected_seq {
+                    self.gaps += seq.saturating_sub(self.next_expected_seq);
+                }
+                self.next_expected_seq = seq + 1;
+
+                let ts_event_ns = u64::from_le_bytes(buf[8..16].try_into().unwrap());
+                let symbol_id = SymbolId(u16::from_le_bytes(buf[16..18].try_into().unwrap()));
+                if symbol_id.0 as usize >= MAX_SYMBOLS {
+                    // Out of range for `MarketStateManager`'s fixed-size
+                    // array — skip rather than indexing into it, same as
+                    // `MarketStateManager::read_snapshot` does for its
+                    // per-record `symbol_id`.
+                    self.bad_symbols += 1;
+                    continue;
+                }
+                let bid_px_ticks = i64::from_le_bytes(buf[18..26].try_into().unwrap());
+                let bid_qty_lots = i64::from_le_bytes(buf[26..34].try_into().unwrap());
+                let ask_px_ticks = i64::from_le_bytes(buf[34..42].try_into().unwrap());
+                let ask_qty_lots = i64::from_le_bytes(buf[42..50].try_into().unwrap());
+
+                let tob = TopOfBook {
+                    ts_event_ns,
+                    symbol_id,
+                    bid_px_ticks,
+                    bid_qty_lots,
+                    ask_px_ticks,
+                    ask_qty_lots,
+                };
+                manager.update_market_state_tob(&tob);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads one fixed-width record into `buf`. Returns `false` on a clean EOF
+/// right at a record boundary, errors on a short trailing read (a torn
+/// write left by a crash mid-append).
+fn read_record_or_eof(file: &mut File, buf: &mut [u8]) -> io::Result<bool> {
+    let mut total = 0;
+    while total < buf.len() {
+        match file.read(&mut buf[total..])? {
+            0 if total == 0 => return Ok(false),
+            0 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "truncated TOB journal record",
+                ));
+            }
+            n => total += n,
+        }
+    }
+    Ok(true)
+}
@@ -9,10 +9,76 @@
 // the symbol id and market state map , (e.g something like a hashmap)
 
 use std::path::Path;
-use lithos_events::Event;
-use lithos_icc::BroadcastReader;
+use lithos_events::{EVENT_KIND_COUNT, EVENT_KIND_TOP_OF_BOOK, Event};
+use lithos_icc::{BroadcastReader, ReadOutcome};
 use onyx_core::MarketStateManager;
 
+/// Handles one kind of `Event`, updating market state as needed.
+///
+/// Registered per `Event` variant via `OnyxEngineBuilder::with_handler`;
+/// `OnyxEngine` dispatches to the registered handler by discriminant
+/// (`Event::kind()`) instead of a hardcoded match, so a new market-data
+/// variant (depth updates, trades) only needs a new handler registration,
+/// not an edit to the engine itself.
+pub trait EventHandler {
+    fn on_event(&mut self, event: &Event, state: &mut MarketStateManager);
+}
+
+/// Default handler wired up by `OnyxEngine::new`: feeds `Event::TopOfBook`
+/// into `MarketStateManager::update_market_state_tob`.
+struct TobStateHandler;
+
+impl EventHandler for TobStateHandler {
+    fn on_event(&mut self, event: &Event, state: &mut MarketStateManager) {
+        if let Event::TopOfBook(tob) = event {
+            state.update_market_state_tob(tob);
+        }
+    }
+}
+
+/// Builds an `OnyxEngine` with handlers registered per `Event` variant,
+/// component-wiring style (cf. reth's node builder): call `with_handler`
+/// once per `Event` kind you want to act on, then `build` to open the
+/// reader and produce the engine.
+pub struct OnyxEngineBuilder {
+    handlers: Box<[Option<Box<dyn EventHandler>>; EVENT_KIND_COUNT]>,
+}
+
+impl OnyxEngineBuilder {
+    pub fn new() -> Self {
+        Self {
+            handlers: Box::new(std::array::from_fn(|_| None)),
+        }
+    }
+
+    /// Registers `handler` to run for every event whose discriminant is
+    /// `kind` (see `Event::kind` / the `EVENT_KIND_*` constants in
+    /// `lithos_events`). Replaces whatever handler, if any, was previously
+    /// registered for that kind.
+    pub fn with_handler(mut self, kind: usize, handler: impl EventHandler + 'static) -> Self {
+        self.handlers[kind] = Some(Box::new(handler));
+        self
+    }
+
+    /// Opens the ring at `path` and assembles the engine with the handlers
+    /// registered so far.
+    pub fn build<P: AsRef<Path>>(self, path: P) -> std::io::Result<OnyxEngine> {
+        let market_state_manager = MarketStateManager::new();
+        let reader = BroadcastReader::<Event>::open(path)?;
+        Ok(OnyxEngine {
+            market_state_manager,
+            reader,
+            handlers: self.handlers,
+        })
+    }
+}
+
+impl Default for OnyxEngineBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct OnyxEngine {
     // per symbol market state
     // this is the state that will get updated
@@ -23,20 +89,25 @@ pub struct OnyxEngine {
     // we replace the generic with Event enum , this is the type we want to read
     // or more specifically a variant of this type
     pub reader: BroadcastReader<Event>,
+
+    /// Handler table indexed by `Event::kind()`; `None` entries are simply
+    /// skipped, so an unregistered event kind is silently ignored rather
+    /// than dropped via a missing match arm.
+    handlers: Box<[Option<Box<dyn EventHandler>>; EVENT_KIND_COUNT]>,
 }
 
 // Implement the functionality of the engine
 
 impl OnyxEngine {
     // First create/initialise the engine
-    pub fn new <P : AsRef<Path>> (path : P) -> std::io::Result<Self> {
-        let market_state_manager = MarketStateManager::new();
-        // this part can be abstracted ?
-        let reader = BroadcastReader::<Event>::open(path)?;
-        Ok(OnyxEngine {
-            market_state_manager,
-            reader,
-        })
+    //
+    // Equivalent to `OnyxEngineBuilder::new().with_handler(EVENT_KIND_TOP_OF_BOOK,
+    // TobStateHandler).build(path)` — kept as a convenience constructor so existing
+    // callers that only care about TOB updates don't need to touch the builder.
+    pub fn new<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        OnyxEngineBuilder::new()
+            .with_handler(EVENT_KIND_TOP_OF_BOOK, TobStateHandler)
+            .build(path)
     }
 
     // Now we define the run function of the engine
@@ -50,26 +121,28 @@ impl OnyxEngine {
         }
     }
 
-    fn poll_events(&mut self) {
-        // we use while let instead of if let because in if let we process just one event
-        // but in case of while let we keep processing as long as we get events
-        while let Some(event) = self.reader.try_read() {
-            // then we process the event (process as in using that event to calculate state , using state + event)
-            self.process_event(&event);
+    pub fn poll_events(&mut self) {
+        // we use a loop instead of if so we keep processing as long as we get events;
+        // a Lagged outcome just means we skipped ahead, so we keep polling rather than
+        // stopping (the ring already fast-forwarded our cursor past the gap)
+        loop {
+            match self.reader.try_read_outcome() {
+                ReadOutcome::Item(event) => self.process_event(&event),
+                ReadOutcome::Lagged { skipped } => {
+                    eprintln!("OnyxEngine: reader lagged, skipped {skipped} events");
+                }
+                ReadOutcome::Empty => break,
+            }
         }
     }
 
     #[inline]
     fn process_event(&mut self, event: &Event) {
-        // we use the event here to perform calculations and update state
-        // in the start the event will be tob but we will it as the generic T
-        // so we match the event the event with its type and then process accordingly
-        match event {
-            Event::TopOfBook(tob) => {
-                if let Err(e) = self.market_state_manager.update_market_state_tob(tob) {
-                    eprintln!("TOB update failed : {}" , e);
-                }
-            }
+        // dispatch by discriminant into the handler table instead of matching
+        // on the event type directly, so adding a new Event variant only means
+        // registering a new handler, not editing this function
+        if let Some(handler) = &mut self.handlers[event.kind()] {
+            handler.on_event(event, &mut self.market_state_manager);
         }
     }
 }
@@ -0,0 +1,62 @@
+//! Demonstrates the effect of cache-line isolating `RingHeader`'s hot fields
+//! (chunk1-6): a publisher's `write_seq.fetch_add` contends with readers'
+//! `Acquire` loads of the same word regardless of layout, but before the
+//! header redesign that contention also invalidated `flags`/`magic`/the
+//! back-pressure cache for every core touching the ring. `full_hot_path`
+//! spins several reader threads against one writer concurrently so that
+//! cross-core cache traffic (visible via `perf stat -e cache-misses`,
+//! or simply via wall time here) reflects the header's false-sharing
+//! behavior end-to-end, not just a single-threaded publish/read loop.
+
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use lithos_events::TopOfBook;
+use lithos_icc::{BroadcastReader, BroadcastWriter, RingConfig};
+use lithos_perf::{make_test_tob, temp_shm_path};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+const NUM_READERS: usize = 4;
+
+fn full_hot_path(c: &mut Criterion) {
+    let path = temp_shm_path("crit_false_sharing");
+    let cfg = RingConfig::new(65536);
+    let mut writer =
+        BroadcastWriter::<TopOfBook>::create(&path, cfg).expect("failed to create writer");
+    let tob = make_test_tob();
+
+    // Readers run continuously in the background, contending for the same
+    // cache lines as the writer's publish loop, so the benchmarked publish
+    // cost includes the cross-core traffic the header redesign targets.
+    let stop = Arc::new(AtomicBool::new(false));
+    let handles: Vec<_> = (0..NUM_READERS)
+        .map(|_| {
+            let path = path.clone();
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || {
+                let mut reader =
+                    BroadcastReader::<TopOfBook>::open(&path).expect("failed to open reader");
+                while !stop.load(Ordering::Relaxed) {
+                    black_box(reader.try_read());
+                }
+            })
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("broadcast_false_sharing");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("full_hot_path", |b| {
+        b.iter(|| writer.publish(black_box(tob)));
+    });
+
+    drop(group);
+    stop.store(true, Ordering::Relaxed);
+    for h in handles {
+        let _ = h.join();
+    }
+    drop(writer);
+    let _ = std::fs::remove_file(&path);
+}
+
+criterion_group!(benches, full_hot_path);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use lithos_events::TopOfBook;
+use lithos_icc::{BroadcastWriter, DirectJournal, RingConfig};
+use lithos_perf::{make_test_tob, temp_shm_path};
+
+fn bench_publish_no_journal(c: &mut Criterion) {
+    let path = temp_shm_path("crit_journal_off");
+    let cfg = RingConfig::new(65536);
+    let mut writer =
+        BroadcastWriter::<TopOfBook>::create(&path, cfg).expect("failed to create writer");
+    let tob = make_test_tob();
+
+    let mut group = c.benchmark_group("journal");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("publish (journal off)", |b| {
+        b.iter(|| writer.publish(black_box(tob)));
+    });
+
+    drop(group);
+    drop(writer);
+    let _ = std::fs::remove_file(&path);
+}
+
+fn bench_publish_direct_journal(c: &mut Criterion) {
+    let ring_path = temp_shm_path("crit_journal_on_ring");
+    let journal_path = std::env::temp_dir().join(format!(
+        "crit_journal_on_{}.log",
+        std::process::id()
+    ));
+    let cfg = RingConfig::new(65536);
+    let writer =
+        BroadcastWriter::<TopOfBook>::create(&ring_path, cfg).expect("failed to create writer");
+    let mut journal =
+        DirectJournal::create(writer, &journal_path).expect("failed to create direct journal");
+    let tob = make_test_tob();
+
+    let mut group = c.benchmark_group("journal");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("publish (journal on, O_DIRECT)", |b| {
+        b.iter(|| journal.publish(black_box(tob)).expect("journal publish failed"));
+    });
+
+    drop(group);
+    let _ = journal.flush();
+    drop(journal);
+    let _ = std::fs::remove_file(&ring_path);
+    let _ = std::fs::remove_file(&journal_path);
+}
+
+criterion_group!(benches, bench_publish_no_journal, bench_publish_direct_journal,);
+criterion_main!(benches);
@@ -2,8 +2,9 @@ use std::collections::BTreeMap;
 use std::hint::black_box;
 use std::mem::{align_of, size_of};
 use std::path::PathBuf;
-use std::sync::{Arc, Barrier};
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Barrier, Mutex};
+use std::time::{Duration, Instant};
 
 use lithos_events::{SymbolId, TopOfBook};
 use lithos_icc::{BroadcastWriter, RingConfig};
@@ -26,9 +27,28 @@ struct CrossThreadDiag {
     prod_ctx_invol: i64,
     cons_ctx_vol: i64,
     cons_ctx_invol: i64,
+    /// Hardware counter totals over the consumer's measured loop, plus the
+    /// event count they were accumulated over. `None` when `perf_event_open`
+    /// couldn't open the group (EACCES/EPERM, or non-Linux).
+    counters: Option<PerfCounterTotals>,
+    counter_events: usize,
+    /// Total time the consumer thread spent waiting on the run queue
+    /// (descheduled, runnable) during the measured loop, from
+    /// `/proc/self/task/<tid>/schedstat`. `None` where schedstat isn't
+    /// available (non-Linux, or the file couldn't be read).
+    sched_wait_ns: Option<u64>,
+    /// Core ids the producer/consumer were pinned to, and the L3 domain
+    /// each belongs to (`None` where `--placement` wasn't requested, or the
+    /// topology couldn't be read — see `resolve_placement`).
+    producer_core: usize,
+    consumer_core: usize,
+    producer_l3_domain: Option<usize>,
+    consumer_l3_domain: Option<usize>,
 }
 
 fn main() {
+    let cli = CliArgs::parse();
+
     let rusage_start = capture_rusage();
     let cache = get_cache_info();
 
@@ -36,6 +56,8 @@ fn main() {
     let mut cross_diag = CrossThreadDiag::default();
     let mut soak_stats: Option<Stats> = None;
     let mut soak_windows: Vec<serde_json::Value> = Vec::new();
+    let mut soak_counters: Option<PerfCounterTotals> = None;
+    let mut soak_distribution: Vec<(u64, u64)> = Vec::new();
 
     // ═══════════════════════════════════════════════════════════════════════
     // 1. Banner
@@ -62,18 +84,32 @@ fn main() {
     // ═══════════════════════════════════════════════════════════════════════
     // 5. Cross-Thread Pipeline (measured e2e)
     // ═══════════════════════════════════════════════════════════════════════
-    section_pipeline_summary(&estimates, &mut results, &mut cross_diag);
+    section_pipeline_summary(&estimates, &mut results, &mut cross_diag, cli.placement);
 
     // ═══════════════════════════════════════════════════════════════════════
     // 6. Soak Test
     // ═══════════════════════════════════════════════════════════════════════
-    section_soak(&mut results, &mut soak_windows, &mut soak_stats);
+    // Sampled on the same 1s cadence the soak test buckets its own latency
+    // windows on, and started right before it, so sample N lines up with
+    // soak window N — a resource spike shows up against the latency window
+    // it happened in instead of only as an invisible contributor to the
+    // start/end delta below.
+    let resource_monitor = ResourceMonitor::spawn(Duration::from_secs(1));
+    section_soak(
+        &mut results,
+        &mut soak_windows,
+        &mut soak_stats,
+        &mut soak_counters,
+        &mut soak_distribution,
+        cli.soak_metrics_port,
+    );
+    let resource_series = resource_monitor.stop_and_collect();
 
     // ═══════════════════════════════════════════════════════════════════════
     // 7. Resource Usage
     // ═══════════════════════════════════════════════════════════════════════
     let rusage_end = capture_rusage();
-    section_resources(&rusage_start, &rusage_end);
+    section_resources(&rusage_start, &rusage_end, &resource_series);
 
     // ═══════════════════════════════════════════════════════════════════════
     // 8. JSON Output
@@ -85,9 +121,82 @@ fn main() {
         &cross_diag,
         &soak_stats,
         &soak_windows,
+        &soak_counters,
+        &soak_distribution,
         &rusage_start,
         &rusage_end,
+        &resource_series,
+        cli.save_baseline,
+        &cli.influx_endpoint,
     );
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // 9. Baseline Comparison (optional regression gate)
+    // ═══════════════════════════════════════════════════════════════════════
+    if let Some(baseline_path) = &cli.baseline {
+        if !compare_to_baseline(&results, baseline_path) {
+            std::process::exit(1);
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CLI args
+// ═══════════════════════════════════════════════════════════════════════════
+
+struct CliArgs {
+    /// `--baseline <path>`: compare this run's `BenchResult`s against a
+    /// prior saved JSON report and exit non-zero on a guarded regression.
+    baseline: Option<PathBuf>,
+    /// `--save-baseline`: also write this run's results to the fixed
+    /// `results/baseline.json` path, which a later run can pass to
+    /// `--baseline` (that flag always takes an explicit path).
+    save_baseline: bool,
+    /// `--influx-endpoint <url>`: also POST this run's results as InfluxDB
+    /// line protocol to `<url>` (e.g. `http://localhost:8086/write?db=lithos`),
+    /// so a time-series DB can track benchmark results across commits.
+    influx_endpoint: Option<String>,
+    /// `--soak-metrics-port <port>`: serve a `/metrics` JSON endpoint on
+    /// `127.0.0.1:<port>` for the duration of the soak test, so an operator
+    /// (or Prometheus) can watch it mid-run instead of waiting for
+    /// `save_results` at the end.
+    soak_metrics_port: Option<u16>,
+    /// `--placement same-l3|cross-l3`: pin the cross-thread pipeline's
+    /// producer/consumer according to L3 cache topology instead of the
+    /// default hard-pin to cores 0/1. See `resolve_placement`.
+    placement: Option<PlacementMode>,
+}
+
+impl CliArgs {
+    fn parse() -> Self {
+        let mut baseline = None;
+        let mut save_baseline = false;
+        let mut influx_endpoint = None;
+        let mut soak_metrics_port = None;
+        let mut placement = None;
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--baseline" => baseline = args.next().map(PathBuf::from),
+                "--save-baseline" => save_baseline = true,
+                "--influx-endpoint" => influx_endpoint = args.next(),
+                "--soak-metrics-port" => {
+                    soak_metrics_port = args.next().and_then(|s| s.parse().ok());
+                }
+                "--placement" => {
+                    placement = args.next().and_then(|s| PlacementMode::parse(&s));
+                }
+                other => eprintln!("  [ignoring unrecognized argument: {other}]"),
+            }
+        }
+        Self {
+            baseline,
+            save_baseline,
+            influx_endpoint,
+            soak_metrics_port,
+            placement,
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -115,12 +224,18 @@ fn print_banner(cache: &CacheInfo) {
     println!("  criterion micro + cross-thread e2e + soak");
     println!("{bar}\n");
 
-    let os = run_cmd("uname", &["-srm"]).unwrap_or_else(|| "unknown".into());
     let date = run_cmd("date", &["+%Y-%m-%d %H:%M:%S"]).unwrap_or_default();
 
-    println!("  CPU:     {}  ({} cores)", cache.cpu_brand, cache.ncpu);
-    println!("  RAM:     {}", format_bytes(cache.ram_bytes));
-    println!("  OS:      {}", os.trim());
+    println!(
+        "  CPU:     {}  ({} physical / {} logical cores)",
+        cache.cpu_brand, cache.physical_cores, cache.ncpu
+    );
+    println!(
+        "  RAM:     {} total, {} available",
+        format_bytes(cache.ram_bytes),
+        format_bytes(cache.available_ram_bytes)
+    );
+    println!("  OS:      {} (kernel {})", cache.os_version, cache.kernel_version);
     println!("  Date:    {}", date.trim());
 
     println!("\n  Cache Hierarchy:");
@@ -319,15 +434,51 @@ fn thread_rusage_ctx_switches() -> (i64, i64) {
     (usage.ru_nvcsw, usage.ru_nivcsw)
 }
 
+/// `/proc/self/task/<tid>/schedstat` for the calling thread: `(time running
+/// on cpu ns, time waiting on the run queue ns, timeslices)`. Read around
+/// the measured loop (not per-event — it's a cumulative kernel counter, not
+/// a log) to attribute tail latency to scheduling delay versus cache
+/// coherency: an involuntary-ctx-switch count that shows up with a large
+/// run-queue wait delta confirms the consumer was actually descheduled for
+/// that long, rather than just interrupted briefly.
+#[cfg(target_os = "linux")]
+fn read_schedstat() -> Option<(u64, u64, u64)> {
+    let tid = unsafe { libc::syscall(libc::SYS_gettid) };
+    let data = std::fs::read_to_string(format!("/proc/self/task/{tid}/schedstat")).ok()?;
+    let mut fields = data.split_whitespace();
+    let cpu_ns = fields.next()?.parse().ok()?;
+    let wait_ns = fields.next()?.parse().ok()?;
+    let slices = fields.next()?.parse().ok()?;
+    Some((cpu_ns, wait_ns, slices))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_schedstat() -> Option<(u64, u64, u64)> {
+    None
+}
+
 fn section_pipeline_summary(
     estimates: &BTreeMap<String, CriterionEstimate>,
     results: &mut Vec<BenchResult>,
     diag: &mut CrossThreadDiag,
+    placement: Option<PlacementMode>,
 ) {
     let shm = temp_shm_path("xthread");
     let num_events = 200_000usize;
     let corpus = generate_replay_corpus(num_events);
 
+    let (producer_core, consumer_core, producer_l3_domain, consumer_l3_domain) = resolve_placement(placement);
+    match (producer_l3_domain, consumer_l3_domain) {
+        (Some(p), Some(c)) => println!(
+            "  Placement: producer=core{producer_core} (L3 domain {p})  consumer=core{consumer_core} (L3 domain {c})"
+        ),
+        _ => println!("  Placement: producer=core{producer_core}  consumer=core{consumer_core} (topology unavailable)"),
+    }
+    diag.producer_core = producer_core;
+    diag.consumer_core = consumer_core;
+    diag.producer_l3_domain = producer_l3_domain;
+    diag.consumer_l3_domain = consumer_l3_domain;
+
     BroadcastWriter::<TopOfBook>::create(&shm, RingConfig::new(65536)).expect("create ring");
 
     // Warmup using production ObsidianProcessor
@@ -344,13 +495,18 @@ fn section_pipeline_summary(
     let shm2 = shm.clone();
 
     let consumer = std::thread::spawn(move || {
-        set_thread_affinity(1);
+        set_thread_affinity(consumer_core);
         let mut engine = OnyxEngine::new(&shm2).expect("onyx engine");
         let mut samples = Vec::with_capacity(num_events);
         // Drain stale data
         while engine.reader.try_read().is_some() {}
 
         let (vol_before, invol_before) = thread_rusage_ctx_switches();
+        let sched_before = read_schedstat();
+        let counters = PerfCounters::open();
+        if let Some(c) = &counters {
+            c.reset_and_enable();
+        }
         b2.wait();
         let baseline_ts = obs_now_ns();
 
@@ -389,15 +545,33 @@ fn section_pipeline_summary(
                 std::hint::spin_loop();
             }
         }
+        let counter_totals = counters.map(|c| {
+            c.disable();
+            c.read_totals()
+        });
+        let sched_wait_ns = match (sched_before, read_schedstat()) {
+            (Some((_, wait_before, _)), Some((_, wait_after, _))) => {
+                Some(wait_after.saturating_sub(wait_before))
+            }
+            _ => None,
+        };
         let (vol_after, invol_after) = thread_rusage_ctx_switches();
         let overruns = engine.reader.overruns();
         let ctx_vol = (vol_after - vol_before).max(0);
         let ctx_invol = (invol_after - invol_before).max(0);
-        (samples, overruns, filtered, ctx_vol, ctx_invol)
+        (
+            samples,
+            overruns,
+            filtered,
+            ctx_vol,
+            ctx_invol,
+            counter_totals,
+            sched_wait_ns,
+        )
     });
 
     barrier.wait();
-    set_thread_affinity(0);
+    set_thread_affinity(producer_core);
 
     let (prod_vol_before, prod_invol_before) = thread_rusage_ctx_switches();
 
@@ -414,7 +588,7 @@ fn section_pipeline_summary(
     let prod_ctx_vol = (prod_vol_after - prod_vol_before).max(0);
     let prod_ctx_invol = (prod_invol_after - prod_invol_before).max(0);
 
-    let (samples, overruns, filtered, cons_ctx_vol, cons_ctx_invol) =
+    let (samples, overruns, filtered, cons_ctx_vol, cons_ctx_invol, counter_totals, sched_wait_ns) =
         consumer.join().expect("consumer thread panicked");
     let _ = std::fs::remove_file(&shm);
 
@@ -466,6 +640,9 @@ fn section_pipeline_summary(
         diag.prod_ctx_invol = prod_ctx_invol;
         diag.cons_ctx_vol = cons_ctx_vol;
         diag.cons_ctx_invol = cons_ctx_invol;
+        diag.counters = counter_totals.clone();
+        diag.counter_events = samples.len();
+        diag.sched_wait_ns = sched_wait_ns;
 
         println!(
             "  {:<30} {:>10} {:>10} {:>10} {:>10}",
@@ -517,6 +694,27 @@ fn section_pipeline_summary(
             prod_ctx_vol, prod_ctx_invol, cons_ctx_vol, cons_ctx_invol,
         );
 
+        match sched_wait_ns {
+            Some(wait_ns) => println!(
+                "  Consumer run-queue wait:  {} total  ({:.1} ns/event)  — confirms whether invol ctx switches above actually descheduled the consumer",
+                format_ns(wait_ns as f64),
+                wait_ns as f64 / samples.len() as f64,
+            ),
+            None => println!("  Consumer run-queue wait: unavailable (schedstat not readable, or non-Linux)"),
+        }
+
+        match &counter_totals {
+            Some(c) => println!(
+                "  Consumer PMU:  IPC={:.2}  cache-misses/event={:.2}  branch-misses/event={:.2}",
+                c.ipc(),
+                c.cache_misses_per_op(samples.len()),
+                c.branch_misses_per_op(samples.len()),
+            ),
+            None => println!(
+                "  PMU counters unavailable (perf_event_paranoid too strict, missing CAP_PERFMON, or non-Linux)"
+            ),
+        }
+
         println!(
             "\n  {}K events | {} symbols | {} overruns | {} filtered",
             num_events / 1000,
@@ -550,13 +748,77 @@ fn section_pipeline_summary(
 // Catches: thermal/freq drift, throughput stability, tail growth, regressions
 // ═══════════════════════════════════════════════════════════════════════════
 
+/// Rolling soak-test state, refreshed at each window close and served over
+/// `/metrics` — lets an operator (or Prometheus) watch a multi-hour soak
+/// drift without waiting for `save_results` at the end of the run.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+struct SoakMetricsSnapshot {
+    events: u64,
+    elapsed_ns: u64,
+    throughput_meps: f64,
+    latency_p50_ns: u64,
+    latency_p99_ns: u64,
+    latency_max_ns: u64,
+    throughput_cv_pct: f64,
+    overruns: u64,
+    minor_faults: i64,
+    major_faults: i64,
+    vol_ctx_switches: i64,
+    invol_ctx_switches: i64,
+}
+
+/// Spawns a background thread serving `snapshot` as JSON on `GET /metrics`
+/// over plain HTTP — no `tiny_http` dependency for a single read-only route.
+/// The thread is never explicitly stopped; it dies with the process when
+/// `main` returns, same as every other soak-test resource.
+fn spawn_soak_metrics_server(port: u16, snapshot: Arc<Mutex<SoakMetricsSnapshot>>) -> std::io::Result<()> {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut buf = [0u8; 1024];
+            let Ok(n) = stream.read(&mut buf) else { continue };
+            let request_line = String::from_utf8_lossy(&buf[..n]);
+            let path = request_line.lines().next().and_then(|l| l.split_whitespace().nth(1)).unwrap_or("");
+
+            let (status, body) = if path == "/metrics" {
+                let body = serde_json::to_string_pretty(&*snapshot.lock().unwrap()).unwrap_or_default();
+                ("200 OK", body)
+            } else {
+                ("404 Not Found", "{\"error\":\"unknown route, try /metrics\"}".to_string())
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len(),
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    Ok(())
+}
+
 fn section_soak(
     results: &mut Vec<BenchResult>,
     windows: &mut Vec<serde_json::Value>,
     out_stats: &mut Option<Stats>,
+    out_counters: &mut Option<PerfCounterTotals>,
+    out_distribution: &mut Vec<(u64, u64)>,
+    metrics_port: Option<u16>,
 ) {
     section_header("SOAK TEST (5s sustained, 256 symbols)");
 
+    let metrics = Arc::new(Mutex::new(SoakMetricsSnapshot::default()));
+    if let Some(port) = metrics_port {
+        match spawn_soak_metrics_server(port, Arc::clone(&metrics)) {
+            Ok(()) => println!("  Live metrics: http://127.0.0.1:{port}/metrics"),
+            Err(e) => eprintln!("  [soak metrics server: couldn't bind 127.0.0.1:{port}: {e}]"),
+        }
+    }
+    let rusage_baseline = capture_rusage();
+
     let shm = temp_shm_path("soak_real");
     BroadcastWriter::<TopOfBook>::create(&shm, RingConfig::new(65536)).expect("create ring");
 
@@ -579,11 +841,16 @@ fn section_soak(
     let check_interval = 50_000u64;
 
     let mut total = 0u64;
-    let mut all_latencies = Vec::with_capacity(100_000);
-    let mut window_latencies: Vec<u64> = Vec::with_capacity(20_000);
+    let mut all_latencies = LatencyHistogram::new();
+    let mut window_latencies = LatencyHistogram::new();
     let mut window_count = 0u64;
     let mut window_idx = 1usize;
 
+    let counters = PerfCounters::open();
+    if let Some(c) = &counters {
+        c.reset_and_enable();
+    }
+
     let start = mono_now_ns();
     let mut window_start = start;
 
@@ -605,8 +872,8 @@ fn section_soak(
         if sample {
             let t1 = mono_now_ns();
             let lat = t1.saturating_sub(t0);
-            all_latencies.push(lat);
-            window_latencies.push(lat);
+            all_latencies.record(lat);
+            window_latencies.record(lat);
         }
 
         if total.is_multiple_of(check_interval) {
@@ -616,14 +883,25 @@ fn section_soak(
                 let tput = window_count as f64 / (elapsed as f64 / 1e9);
 
                 // Per-window latency stats for tail-growth detection
-                let (wp50, wp99, wmax) = if !window_latencies.is_empty() {
-                    let mut wl = std::mem::take(&mut window_latencies);
-                    let ws = compute_stats(&mut wl);
-                    (ws.p50, ws.p99, ws.max)
+                let (wp50, wp99, wmax) = if window_latencies.count() > 0 {
+                    (
+                        window_latencies.percentile(50.0),
+                        window_latencies.percentile(99.0),
+                        window_latencies.max(),
+                    )
                 } else {
                     (0, 0, 0)
                 };
 
+                // Frequency/thermal snapshot for cores 0 and 1, the cores
+                // the cross-thread section pins producer/consumer to when
+                // `--placement` isn't given —
+                // explains throughput dips the CV% gate flags as governor
+                // downclocking or thermal throttling rather than noise.
+                let freq0 = cpu_scaling_freq_khz(0);
+                let freq1 = cpu_scaling_freq_khz(1);
+                let temp = highest_thermal_zone_millic();
+
                 windows.push(serde_json::json!({
                     "second": window_idx,
                     "events": window_count,
@@ -632,18 +910,46 @@ fn section_soak(
                     "latency_p50_ns": wp50,
                     "latency_p99_ns": wp99,
                     "latency_max_ns": wmax,
+                    "cpu_freq_khz": [freq0, freq1],
+                    "temp_millic": temp,
                 }));
-                println!(
+
+                if metrics_port.is_some() {
+                    let rusage_now = capture_rusage();
+                    let mut snap = metrics.lock().unwrap();
+                    *snap = SoakMetricsSnapshot {
+                        events: total,
+                        elapsed_ns: now - start,
+                        throughput_meps: tput / 1e6,
+                        latency_p50_ns: wp50,
+                        latency_p99_ns: wp99,
+                        latency_max_ns: wmax,
+                        throughput_cv_pct: soak_throughput_cv(windows.as_slice()),
+                        overruns: engine.reader.overruns(),
+                        minor_faults: rusage_now.minor_faults.saturating_sub(rusage_baseline.minor_faults),
+                        major_faults: rusage_now.major_faults.saturating_sub(rusage_baseline.major_faults),
+                        vol_ctx_switches: rusage_now.vol_ctx_switches.saturating_sub(rusage_baseline.vol_ctx_switches),
+                        invol_ctx_switches: rusage_now.invol_ctx_switches.saturating_sub(rusage_baseline.invol_ctx_switches),
+                    };
+                }
+
+                print!(
                     "  Second {:<3}: {:>10} events  {:>8.1} M/s  p50={:>4} ns  p99={:>4} ns  max={:>6} ns",
                     window_idx,
                     format_count(window_count),
                     tput / 1e6,
                     wp50, wp99, wmax,
                 );
+                match (freq0.or(freq1), temp) {
+                    (Some(f), Some(t)) => println!("  cpu={:.2}GHz  temp={:.1}C", f as f64 / 1e6, t as f64 / 1000.0),
+                    (Some(f), None) => println!("  cpu={:.2}GHz", f as f64 / 1e6),
+                    (None, Some(t)) => println!("  temp={:.1}C", t as f64 / 1000.0),
+                    (None, None) => println!(),
+                }
                 window_idx += 1;
                 window_start = now;
                 window_count = 0;
-                window_latencies = Vec::with_capacity(20_000);
+                window_latencies = LatencyHistogram::new();
             }
             if now - start >= duration_ns {
                 break;
@@ -651,10 +957,26 @@ fn section_soak(
         }
     }
 
+    let counter_totals = counters.map(|c| {
+        c.disable();
+        c.read_totals()
+    });
+
     let total_elapsed = mono_now_ns() - start;
     let overall_tput = total as f64 / (total_elapsed as f64 / 1e9);
     let overruns = engine.reader.overruns();
 
+    match &counter_totals {
+        Some(c) => println!(
+            "  PMU:  IPC={:.2}  cache-misses/event={:.2}  branch-misses/event={:.2}",
+            c.ipc(),
+            c.cache_misses_per_op(total as usize),
+            c.branch_misses_per_op(total as usize),
+        ),
+        None => println!("  PMU counters unavailable (perf_event_paranoid, missing CAP_PERFMON, or non-Linux)"),
+    }
+    *out_counters = counter_totals;
+
     println!(
         "\n  Total: {} events in {:.2}s ({:.1} M/s) | {} overruns",
         format_count(total),
@@ -663,11 +985,11 @@ fn section_soak(
         overruns,
     );
 
-    if !all_latencies.is_empty() {
-        let stats = compute_stats(&mut all_latencies);
+    if all_latencies.count() > 0 {
+        let stats = all_latencies.stats();
         println!(
-            "  Aggregate: p50={} ns  p90={} ns  p99={} ns  p99.9={} ns  max={} ns",
-            stats.p50, stats.p90, stats.p99, stats.p999, stats.max
+            "  Aggregate: p50={} ns  p90={} ns  p99={} ns  p99.9={} ns  p99.99={} ns  p99.999={} ns  max={} ns",
+            stats.p50, stats.p90, stats.p99, stats.p999, stats.p9999, stats.p99999, stats.max
         );
 
         // Throughput stability (CV%)
@@ -689,6 +1011,7 @@ fn section_soak(
             }
         }
 
+        *out_distribution = all_latencies.distribution();
         *out_stats = Some(stats.clone());
         results.push(BenchResult {
             name: "soak_latency".into(),
@@ -704,7 +1027,113 @@ fn section_soak(
 // Resources
 // ═══════════════════════════════════════════════════════════════════════════
 
-fn section_resources(start: &ResourceSnapshot, end: &ResourceSnapshot) {
+/// One `ResourceSnapshot` taken mid-run by `ResourceMonitor`, plus the
+/// counters that matter for spike-hunting: `max_rss_bytes` as the gauge it
+/// is, and the fault/ctx-switch counters as the *increase* since the
+/// previous sample rather than raw cumulative totals — a cumulative number
+/// can only grow, so the interesting signal is how fast it moved in this
+/// particular window, not its absolute value.
+#[derive(Debug, Clone, serde::Serialize)]
+struct ResourceSample {
+    /// Nanoseconds since the monitor started — lines up with soak test
+    /// `elapsed_ns`/`second` since both are sampled on the same interval
+    /// starting at roughly the same instant.
+    t_ns: u64,
+    max_rss_bytes: i64,
+    minor_faults_delta: i64,
+    major_faults_delta: i64,
+    vol_ctx_switches_delta: i64,
+    invol_ctx_switches_delta: i64,
+}
+
+/// Background thread sampling `capture_rusage()` at a fixed interval for as
+/// long as the monitored section runs, so a mid-run spike (a GC pause, a
+/// page-fault storm, a scheduler hiccup) shows up in the time series instead
+/// of being averaged away into a single start/end delta.
+struct ResourceMonitor {
+    stop: Arc<AtomicBool>,
+    samples: Arc<Mutex<Vec<ResourceSample>>>,
+    handle: std::thread::JoinHandle<()>,
+}
+
+impl ResourceMonitor {
+    fn spawn(interval: Duration) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let stop2 = Arc::clone(&stop);
+        let samples2 = Arc::clone(&samples);
+        let handle = std::thread::spawn(move || {
+            let start = mono_now_ns();
+            let mut prev = capture_rusage();
+            while !stop2.load(Ordering::Relaxed) {
+                std::thread::sleep(interval);
+                let now = capture_rusage();
+                samples2.lock().unwrap().push(ResourceSample {
+                    t_ns: mono_now_ns() - start,
+                    max_rss_bytes: now.max_rss_bytes,
+                    minor_faults_delta: now.minor_faults.saturating_sub(prev.minor_faults),
+                    major_faults_delta: now.major_faults.saturating_sub(prev.major_faults),
+                    vol_ctx_switches_delta: now.vol_ctx_switches.saturating_sub(prev.vol_ctx_switches),
+                    invol_ctx_switches_delta: now.invol_ctx_switches.saturating_sub(prev.invol_ctx_switches),
+                });
+                prev = now;
+            }
+        });
+        Self { stop, samples, handle }
+    }
+
+    /// Signals the sampling loop to stop after its current sleep and blocks
+    /// until it exits, returning everything it collected.
+    fn stop_and_collect(self) -> Vec<ResourceSample> {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.handle.join();
+        Arc::try_unwrap(self.samples)
+            .map(|m| m.into_inner().unwrap())
+            .unwrap_or_default()
+    }
+}
+
+/// min/max/mean across a resource counter's samples, plus the timestamp the
+/// max occurred at — the piece a single start/end delta can't give you.
+fn summarize_counter(series: &[ResourceSample], value_of: impl Fn(&ResourceSample) -> i64) -> serde_json::Value {
+    if series.is_empty() {
+        return serde_json::json!({ "min": 0, "max": 0, "mean": 0.0, "peak_t_ns": 0 });
+    }
+    let mut min = i64::MAX;
+    let mut max = i64::MIN;
+    let mut max_t_ns = 0u64;
+    let mut sum = 0i64;
+    for s in series {
+        let v = value_of(s);
+        sum += v;
+        if v < min {
+            min = v;
+        }
+        if v > max {
+            max = v;
+            max_t_ns = s.t_ns;
+        }
+    }
+    serde_json::json!({
+        "min": min,
+        "max": max,
+        "mean": sum as f64 / series.len() as f64,
+        "peak_t_ns": max_t_ns,
+    })
+}
+
+fn summarize_resource_series(series: &[ResourceSample]) -> serde_json::Value {
+    serde_json::json!({
+        "sample_count": series.len(),
+        "max_rss_bytes": summarize_counter(series, |s| s.max_rss_bytes),
+        "minor_faults_per_sample": summarize_counter(series, |s| s.minor_faults_delta),
+        "major_faults_per_sample": summarize_counter(series, |s| s.major_faults_delta),
+        "vol_ctx_switches_per_sample": summarize_counter(series, |s| s.vol_ctx_switches_delta),
+        "invol_ctx_switches_per_sample": summarize_counter(series, |s| s.invol_ctx_switches_delta),
+    })
+}
+
+fn section_resources(start: &ResourceSnapshot, end: &ResourceSnapshot, resource_series: &[ResourceSample]) {
     section_header("RESOURCE USAGE");
 
     let delta_minor = end.minor_faults.saturating_sub(start.minor_faults);
@@ -732,6 +1161,21 @@ fn section_resources(start: &ResourceSnapshot, end: &ResourceSnapshot) {
         "  System CPU time:             {:.3}s",
         delta_sys_us as f64 / 1e6
     );
+
+    if let Some(worst) = resource_series
+        .iter()
+        .max_by_key(|s| s.major_faults_delta + s.invol_ctx_switches_delta)
+    {
+        if worst.major_faults_delta > 0 || worst.invol_ctx_switches_delta > 0 {
+            println!(
+                "  Worst sampled window: +{}ms  major_faults={}  invol_ctx_switches={}  (soak second ~{})",
+                worst.t_ns / 1_000_000,
+                worst.major_faults_delta,
+                worst.invol_ctx_switches_delta,
+                worst.t_ns / 1_000_000_000 + 1,
+            );
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -746,12 +1190,21 @@ fn save_results(
     cross_diag: &CrossThreadDiag,
     soak_stats: &Option<Stats>,
     soak_windows: &[serde_json::Value],
+    soak_counters: &Option<PerfCounterTotals>,
+    soak_distribution: &[(u64, u64)],
     rusage_start: &ResourceSnapshot,
     rusage_end: &ResourceSnapshot,
+    resource_series: &[ResourceSample],
+    save_baseline: bool,
+    influx_endpoint: &Option<String>,
 ) {
     let timestamp = run_cmd("date", &["+%Y%m%d_%H%M%S"])
         .map(|s| s.trim().to_string())
         .unwrap_or_else(|| "unknown".into());
+    let timestamp_ns = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
 
     let results_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/results");
     let _ = std::fs::create_dir_all(results_dir);
@@ -770,14 +1223,41 @@ fn save_results(
             "stats": cross_diag.stats,
             "overruns": cross_diag.overruns,
             "filtered": cross_diag.filtered,
+            "placement": {
+                "producer_core": cross_diag.producer_core,
+                "consumer_core": cross_diag.consumer_core,
+                "producer_l3_domain": cross_diag.producer_l3_domain,
+                "consumer_l3_domain": cross_diag.consumer_l3_domain,
+            },
             "context_switches": {
                 "producer": { "voluntary": cross_diag.prod_ctx_vol, "involuntary": cross_diag.prod_ctx_invol },
                 "consumer": { "voluntary": cross_diag.cons_ctx_vol, "involuntary": cross_diag.cons_ctx_invol },
             },
+            "counters": cross_diag.counters.as_ref().map(|c| serde_json::json!({
+                "cycles": c.cycles,
+                "instructions": c.instructions,
+                "cache_misses": c.cache_misses,
+                "branch_misses": c.branch_misses,
+                "ipc": c.ipc(),
+                "cache_misses_per_event": c.cache_misses_per_op(cross_diag.counter_events),
+                "branch_misses_per_event": c.branch_misses_per_op(cross_diag.counter_events),
+            })),
+            "sched_wait_ns_total": cross_diag.sched_wait_ns,
+            "sched_wait_ns_per_event": cross_diag.sched_wait_ns.map(|w| {
+                if cross_diag.counter_events == 0 {
+                    0.0
+                } else {
+                    w as f64 / cross_diag.counter_events as f64
+                }
+            }),
         },
         "soak": {
             "windows": soak_windows,
             "latency": soak_stats,
+            "counters": soak_counters,
+            // (representative_value_ns, count) pairs for non-empty buckets
+            // only, so downstream tooling can render a full latency CDF.
+            "latency_distribution": soak_distribution,
         },
         "resources": {
             "start": rusage_start,
@@ -789,12 +1269,20 @@ fn save_results(
                 "invol_ctx_switches": rusage_end.invol_ctx_switches.saturating_sub(rusage_start.invol_ctx_switches),
                 "user_time_us": rusage_end.user_time_us.saturating_sub(rusage_start.user_time_us),
                 "sys_time_us": rusage_end.sys_time_us.saturating_sub(rusage_start.sys_time_us),
-            }
+            },
+            // Sampled on the soak test's own 1s cadence (see
+            // `ResourceMonitor`), so `series[i]` corresponds to soak window
+            // `i + 1` — a resource spike can be matched to the latency
+            // window it happened in instead of only showing up here as a
+            // contribution to the coarse start/end delta above.
+            "series": resource_series,
+            "series_summary": summarize_resource_series(resource_series),
         },
     });
 
+    let json_text = serde_json::to_string_pretty(&output).unwrap();
     let bar = "\u{2550}".repeat(90);
-    match std::fs::write(&json_path, serde_json::to_string_pretty(&output).unwrap()) {
+    match std::fs::write(&json_path, &json_text) {
         Ok(()) => {
             println!("\n{bar}");
             println!("  Results saved to: {json_path}");
@@ -802,12 +1290,405 @@ fn save_results(
         }
         Err(e) => eprintln!("\n  [failed to save results: {e}]\n"),
     }
+
+    if save_baseline {
+        let baseline_path = format!("{results_dir}/baseline.json");
+        match std::fs::write(&baseline_path, &json_text) {
+            Ok(()) => println!("  Baseline promoted: {baseline_path}\n"),
+            Err(e) => eprintln!("  [failed to save baseline: {e}]\n"),
+        }
+    }
+
+    if let Some(endpoint) = influx_endpoint {
+        let lines = build_influx_lines(results, cross_diag, soak_stats, soak_windows, soak_counters, timestamp_ns);
+        match post_line_protocol(endpoint, &lines) {
+            Ok(()) => println!("  Pushed {} line(s) to {endpoint}\n", lines.lines().count()),
+            Err(e) => eprintln!("  [failed to push to influx endpoint {endpoint}: {e}]\n"),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Baseline Comparison
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Parsed subset of a previously saved `save_results` JSON file — just the
+/// stage benchmarks comparison keys on.
+#[derive(serde::Deserialize)]
+struct SavedReport {
+    stage_benchmarks: Vec<BenchResult>,
+}
+
+/// Relative-regression thresholds: a metric only fails the gate if it moves
+/// worse than this percentage versus baseline. p999 is reported for context
+/// but isn't gated — it's noisy enough at soak sample rates that a fixed
+/// threshold would cry wolf.
+const P50_REGRESSION_PCT: f64 = 5.0;
+const P99_REGRESSION_PCT: f64 = 10.0;
+
+/// Loads `baseline_path`, matches entries in `results` by name, and prints a
+/// diff table of p50/p99/p999 versus baseline. Returns `false` if any entry
+/// regressed past `P50_REGRESSION_PCT`/`P99_REGRESSION_PCT`, in which case
+/// the caller should exit non-zero.
+fn compare_to_baseline(results: &[BenchResult], baseline_path: &std::path::Path) -> bool {
+    section_header("BASELINE COMPARISON");
+
+    let data = match std::fs::read_to_string(baseline_path) {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!(
+                "  [baseline: couldn't read {}: {e} — skipping comparison]",
+                baseline_path.display()
+            );
+            return true;
+        }
+    };
+    let baseline: SavedReport = match serde_json::from_str(&data) {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!(
+                "  [baseline: couldn't parse {}: {e} — skipping comparison]",
+                baseline_path.display()
+            );
+            return true;
+        }
+    };
+    let baseline_by_name: BTreeMap<String, Stats> = baseline
+        .stage_benchmarks
+        .into_iter()
+        .map(|r| (r.name, r.stats))
+        .collect();
+
+    println!(
+        "  {:<26} {:>20} {:>20} {:>20}",
+        "Benchmark", "p50 ns", "p99 ns", "p999 ns"
+    );
+    println!("  {}", "\u{2500}".repeat(94));
+
+    let mut regressed = false;
+    for r in results {
+        let Some(base) = baseline_by_name.get(&r.name) else {
+            println!("  {:<26} (new, no baseline entry)", r.name);
+            continue;
+        };
+
+        let d50 = pct_delta(base.p50, r.stats.p50);
+        let d99 = pct_delta(base.p99, r.stats.p99);
+        let d999 = pct_delta(base.p999, r.stats.p999);
+        let regression = d50 > P50_REGRESSION_PCT || d99 > P99_REGRESSION_PCT;
+        regressed |= regression;
+
+        println!(
+            "  {:<26} {:>8} {} {:>7.1}%   {:>8} {} {:>7.1}%   {:>8} {} {:>7.1}%{}",
+            r.name,
+            r.stats.p50, delta_arrow(d50), d50,
+            r.stats.p99, delta_arrow(d99), d99,
+            r.stats.p999, delta_arrow(d999), d999,
+            if regression { "   REGRESSION" } else { "" },
+        );
+    }
+
+    if regressed {
+        println!(
+            "\n  FAIL: one or more metrics regressed past +{P50_REGRESSION_PCT}% p50 / +{P99_REGRESSION_PCT}% p99"
+        );
+    } else {
+        println!("\n  PASS: no guarded metric regressed past threshold");
+    }
+
+    !regressed
+}
+
+fn pct_delta(base: u64, current: u64) -> f64 {
+    if base == 0 {
+        0.0
+    } else {
+        (current as f64 - base as f64) / base as f64 * 100.0
+    }
+}
+
+fn delta_arrow(pct: f64) -> &'static str {
+    if pct > 0.5 {
+        "\u{2191}"
+    } else if pct < -0.5 {
+        "\u{2193}"
+    } else {
+        "="
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// InfluxDB Export
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Builds one InfluxDB line-protocol line per `BenchResult`, one for the
+/// cross-thread pipeline diagnostics, and one per soak window — all sharing
+/// `timestamp_ns` as the point time, since they all describe the same run.
+fn build_influx_lines(
+    results: &[BenchResult],
+    cross_diag: &CrossThreadDiag,
+    soak_stats: &Option<Stats>,
+    soak_windows: &[serde_json::Value],
+    soak_counters: &Option<PerfCounterTotals>,
+    timestamp_ns: u128,
+) -> String {
+    let mut lines = String::new();
+
+    for r in results {
+        lines.push_str(&format!(
+            "lithos_bench,stage={},unit={} mean={},p50={}i,p99={}i,p999={}i,min={}i,max={}i,stddev={},count={}i {timestamp_ns}\n",
+            influx_escape_tag(&r.name),
+            influx_escape_tag(&r.unit),
+            r.stats.mean,
+            r.stats.p50,
+            r.stats.p99,
+            r.stats.p999,
+            r.stats.min,
+            r.stats.max,
+            r.stats.stddev,
+            r.stats.count,
+        ));
+    }
+
+    let mut cross_fields = vec![
+        format!("overruns={}i", cross_diag.overruns),
+        format!("filtered={}i", cross_diag.filtered),
+        format!("prod_ctx_vol={}i", cross_diag.prod_ctx_vol),
+        format!("prod_ctx_invol={}i", cross_diag.prod_ctx_invol),
+        format!("cons_ctx_vol={}i", cross_diag.cons_ctx_vol),
+        format!("cons_ctx_invol={}i", cross_diag.cons_ctx_invol),
+    ];
+    if let Some(c) = &cross_diag.counters {
+        cross_fields.push(format!("ipc={}", c.ipc()));
+    }
+    if let Some(s) = soak_stats {
+        cross_fields.push(format!("soak_p99={}i", s.p99));
+    }
+    lines.push_str(&format!(
+        "lithos_bench_cross_thread {} {timestamp_ns}\n",
+        cross_fields.join(",")
+    ));
+
+    if let Some(c) = soak_counters {
+        lines.push_str(&format!(
+            "lithos_bench_soak cv={},ipc={} {timestamp_ns}\n",
+            soak_throughput_cv(soak_windows),
+            c.ipc(),
+        ));
+    } else if !soak_windows.is_empty() {
+        lines.push_str(&format!(
+            "lithos_bench_soak cv={} {timestamp_ns}\n",
+            soak_throughput_cv(soak_windows),
+        ));
+    }
+
+    for w in soak_windows {
+        let Some(second) = w.get("second").and_then(|v| v.as_u64()) else {
+            continue;
+        };
+        lines.push_str(&format!(
+            "lithos_bench_soak_window,second={second} throughput_meps={},latency_p50_ns={}i,latency_p99_ns={}i,latency_max_ns={}i {timestamp_ns}\n",
+            w.get("throughput_meps").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            w.get("latency_p50_ns").and_then(|v| v.as_u64()).unwrap_or(0),
+            w.get("latency_p99_ns").and_then(|v| v.as_u64()).unwrap_or(0),
+            w.get("latency_max_ns").and_then(|v| v.as_u64()).unwrap_or(0),
+        ));
+    }
+
+    lines
+}
+
+/// Coefficient of variation (%) of per-window throughput — same computation
+/// `section_soak` prints inline, recomputed here from the saved windows
+/// rather than threaded through as its own out-param.
+fn soak_throughput_cv(soak_windows: &[serde_json::Value]) -> f64 {
+    let tputs: Vec<f64> = soak_windows
+        .iter()
+        .filter_map(|w| w.get("throughput_meps").and_then(|v| v.as_f64()))
+        .collect();
+    if tputs.is_empty() {
+        return 0.0;
+    }
+    let mean = tputs.iter().sum::<f64>() / tputs.len() as f64;
+    if mean == 0.0 {
+        return 0.0;
+    }
+    let var = tputs.iter().map(|&t| (t - mean) * (t - mean)).sum::<f64>() / tputs.len() as f64;
+    var.sqrt() / mean * 100.0
+}
+
+/// Escapes the characters line protocol treats specially in tag keys/values
+/// (comma, space, equals) with a backslash. Stage names and units in this
+/// tool are plain identifiers, so this is a defensive formality more than
+/// something expected to ever fire.
+fn influx_escape_tag(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(' ', "\\ ")
+        .replace('=', "\\=")
+}
+
+/// Posts `body` to `endpoint` as a raw HTTP/1.1 request over a plain TCP
+/// socket — no `http`/`reqwest` dependency for what's a one-shot POST with a
+/// fixed body. Only `http://` endpoints are supported (no TLS).
+fn post_line_protocol(endpoint: &str, body: &str) -> std::io::Result<()> {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    let rest = endpoint
+        .strip_prefix("http://")
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "endpoint must start with http://"))?;
+    let (authority, path) = rest.split_once('/').map(|(a, p)| (a, format!("/{p}"))).unwrap_or((rest, "/".to_string()));
+    let (host, port) = authority.split_once(':').map(|(h, p)| (h, p.parse().unwrap_or(80))).unwrap_or((authority, 80));
+
+    let mut stream = TcpStream::connect((host, port))?;
+    let request = format!(
+        "POST {path} HTTP/1.1\r\nHost: {host}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    );
+    stream.write_all(request.as_bytes())?;
+
+    // Drain the response so the server isn't left hanging on a half-closed
+    // write; we don't parse the status line beyond logging it on failure.
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let status_line = response.lines().next().unwrap_or("");
+    if !status_line.contains(" 200") && !status_line.contains(" 204") {
+        return Err(std::io::Error::other(format!("unexpected response: {status_line}")));
+    }
+    Ok(())
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Helpers
 // ═══════════════════════════════════════════════════════════════════════════
 
+// ═══════════════════════════════════════════════════════════════════════════
+// Cache Topology & Thread Placement
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Explicit producer/consumer placement relative to the L3 cache topology,
+/// chosen via `--placement`. The default (no flag) keeps the historical
+/// hard-pin to cores 0/1 regardless of topology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlacementMode {
+    /// Pin producer and consumer to sibling cores that share an L3 domain —
+    /// the cheap case, cache-line traffic never crosses the interconnect.
+    SameL3,
+    /// Pin producer and consumer to cores in different L3 domains (distinct
+    /// chiplets/CCXs on multi-chiplet parts) — exposes the interconnect
+    /// cost the same-L3 placement hides.
+    CrossL3,
+}
+
+impl PlacementMode {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "same-l3" => Some(Self::SameL3),
+            "cross-l3" => Some(Self::CrossL3),
+            _ => None,
+        }
+    }
+}
+
+/// Cores grouped by shared L3 cache domain, parsed from
+/// `/sys/devices/system/cpu/*/cache/index*/shared_cpu_list` — cores whose
+/// `level` file reads `3` and whose `shared_cpu_list` is identical belong to
+/// the same physical last-level cache.
+struct CacheTopology {
+    domain_of: BTreeMap<usize, usize>,
+}
+
+impl CacheTopology {
+    #[cfg(target_os = "linux")]
+    fn detect() -> Option<Self> {
+        let mut domain_id_of_list: BTreeMap<String, usize> = BTreeMap::new();
+        let mut domain_of: BTreeMap<usize, usize> = BTreeMap::new();
+
+        for entry in std::fs::read_dir("/sys/devices/system/cpu").ok()?.flatten() {
+            let name = entry.file_name();
+            let Some(cpu) = name.to_str().and_then(|n| n.strip_prefix("cpu")).and_then(|n| n.parse::<usize>().ok())
+            else {
+                continue;
+            };
+            let Ok(cache_indices) = std::fs::read_dir(entry.path().join("cache")) else {
+                continue;
+            };
+            for idx in cache_indices.flatten() {
+                let Ok(level) = std::fs::read_to_string(idx.path().join("level")) else {
+                    continue;
+                };
+                if level.trim() != "3" {
+                    continue;
+                }
+                let Ok(list) = std::fs::read_to_string(idx.path().join("shared_cpu_list")) else {
+                    continue;
+                };
+                let list = list.trim().to_string();
+                let next_id = domain_id_of_list.len();
+                let domain = *domain_id_of_list.entry(list).or_insert(next_id);
+                domain_of.insert(cpu, domain);
+                break;
+            }
+        }
+
+        if domain_of.is_empty() { None } else { Some(Self { domain_of }) }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn detect() -> Option<Self> {
+        None
+    }
+
+    fn domain_of_core(&self, core: usize) -> Option<usize> {
+        self.domain_of.get(&core).copied()
+    }
+
+    /// Picks one core pair satisfying `mode`, or `None` if the topology
+    /// doesn't have enough cores/domains to satisfy it (e.g. `CrossL3` on a
+    /// single-socket, single-CCX part).
+    fn pick_pair(&self, mode: PlacementMode) -> Option<(usize, usize)> {
+        let mut by_domain: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (&cpu, &domain) in &self.domain_of {
+            by_domain.entry(domain).or_default().push(cpu);
+        }
+        match mode {
+            PlacementMode::SameL3 => by_domain.values().find(|cores| cores.len() >= 2).map(|cores| (cores[0], cores[1])),
+            PlacementMode::CrossL3 => {
+                let mut domains = by_domain.values();
+                let first = domains.next()?;
+                let second = domains.next()?;
+                Some((first[0], second[0]))
+            }
+        }
+    }
+}
+
+/// Resolves `mode` (from `--placement`) into concrete producer/consumer core
+/// ids plus their L3 domain ids, for both thread pinning and the
+/// `cross_thread` JSON report. Falls back to the historical hard-pin
+/// (core 0 for the producer, core 1 for the consumer, domains unreported)
+/// when no mode was requested or the topology can't satisfy it.
+fn resolve_placement(mode: Option<PlacementMode>) -> (usize, usize, Option<usize>, Option<usize>) {
+    let Some(mode) = mode else {
+        return (0, 1, None, None);
+    };
+    let Some(topology) = CacheTopology::detect() else {
+        eprintln!("  [placement: cache topology unavailable, falling back to cores 0/1]");
+        return (0, 1, None, None);
+    };
+    let Some((producer, consumer)) = topology.pick_pair(mode) else {
+        eprintln!("  [placement: topology has no {mode:?} pair, falling back to cores 0/1]");
+        return (0, 1, None, None);
+    };
+    (
+        producer,
+        consumer,
+        topology.domain_of_core(producer),
+        topology.domain_of_core(consumer),
+    )
+}
+
 /// Hint the OS scheduler to run this thread on a distinct core.
 /// macOS: uses thread_affinity_policy (hint, not hard pin).
 /// Linux: uses sched_setaffinity (hard pin).
@@ -854,6 +1735,48 @@ fn set_thread_affinity(tag: usize) {
     }
 }
 
+/// Current scaling frequency of `cpu`, in kHz, from cpufreq sysfs. `None` on
+/// platforms without it (macOS, or a Linux box with no cpufreq governor).
+#[cfg(target_os = "linux")]
+fn cpu_scaling_freq_khz(cpu: usize) -> Option<u64> {
+    std::fs::read_to_string(format!(
+        "/sys/devices/system/cpu/cpu{cpu}/cpufreq/scaling_cur_freq"
+    ))
+    .ok()?
+    .trim()
+    .parse()
+    .ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cpu_scaling_freq_khz(_cpu: usize) -> Option<u64> {
+    None
+}
+
+/// Highest reading across all `/sys/class/thermal/thermal_zone*/temp`
+/// zones, in millidegrees C — a package-level proxy for thermal throttling
+/// without needing to know which zone corresponds to the package on a given
+/// board.
+#[cfg(target_os = "linux")]
+fn highest_thermal_zone_millic() -> Option<u64> {
+    let dir = std::fs::read_dir("/sys/class/thermal").ok()?;
+    dir.flatten()
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .is_some_and(|n| n.starts_with("thermal_zone"))
+        })
+        .filter_map(|e| std::fs::read_to_string(e.path().join("temp")).ok())
+        .filter_map(|s| s.trim().parse::<i64>().ok())
+        .max()
+        .map(|v| v as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn highest_thermal_zone_millic() -> Option<u64> {
+    None
+}
+
 fn run_cmd(cmd: &str, args: &[&str]) -> Option<String> {
     std::process::Command::new(cmd)
         .args(args)
@@ -0,0 +1,232 @@
+//! Linux cache topology and CPU brand detection for `get_cache_info`.
+//!
+//! Mirrors the Apple `sysctlbyname` path in `lib.rs` with what's available
+//! on Linux: cache geometry from sysfs, RAM from `/proc/meminfo`, and CPU
+//! brand from `/proc/cpuinfo`. Falls back to `cpuid` (leaf 4 for cache
+//! descriptors, leaves 0x80000002-0x80000004 for the brand string) when
+//! sysfs isn't mounted/readable, e.g. inside some containers.
+
+use std::path::Path;
+
+pub fn l1d_cache_bytes() -> Option<u64> {
+    sysfs_cache_size(1, "Data").or_else(|| cpuid_cache_size(CpuidCacheKind::L1d))
+}
+
+pub fn l1i_cache_bytes() -> Option<u64> {
+    sysfs_cache_size(1, "Instruction").or_else(|| cpuid_cache_size(CpuidCacheKind::L1i))
+}
+
+pub fn l2_cache_bytes() -> Option<u64> {
+    sysfs_cache_size(2, "Unified")
+        .or_else(|| sysfs_cache_size(2, "Data"))
+        .or_else(|| cpuid_cache_size(CpuidCacheKind::L2))
+}
+
+pub fn cacheline_bytes() -> Option<u64> {
+    sysfs_line_size().or_else(cpuid_line_size)
+}
+
+pub fn total_ram_bytes() -> Option<u64> {
+    let data = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = data.lines().find(|l| l.starts_with("MemTotal:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+pub fn cpu_brand_string() -> Option<String> {
+    proc_cpuinfo_model_name().or_else(cpuid_brand_string)
+}
+
+fn proc_cpuinfo_model_name() -> Option<String> {
+    let data = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    let line = data.lines().find(|l| l.starts_with("model name"))?;
+    let (_, value) = line.split_once(':')?;
+    Some(value.trim().to_string())
+}
+
+// ─── sysfs cache geometry ───────────────────────────────────────────────────
+
+struct SysfsCacheEntry {
+    level: u64,
+    cache_type: String,
+    size_bytes: u64,
+    line_size: u64,
+}
+
+fn read_sysfs_cache_entries() -> Vec<SysfsCacheEntry> {
+    let base = Path::new("/sys/devices/system/cpu/cpu0/cache");
+    let Ok(dir) = std::fs::read_dir(base) else {
+        return Vec::new();
+    };
+
+    let mut out = Vec::new();
+    for entry in dir.flatten() {
+        let path = entry.path();
+        let is_index = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("index"));
+        if !is_index {
+            continue;
+        }
+        let level = read_u64_file(&path.join("level"));
+        let cache_type = std::fs::read_to_string(path.join("type"))
+            .ok()
+            .map(|s| s.trim().to_string());
+        let size_bytes = std::fs::read_to_string(path.join("size"))
+            .ok()
+            .and_then(|s| parse_sysfs_size(s.trim()));
+        let line_size = read_u64_file(&path.join("coherency_line_size")).unwrap_or(0);
+        if let (Some(level), Some(cache_type), Some(size_bytes)) = (level, cache_type, size_bytes)
+        {
+            out.push(SysfsCacheEntry {
+                level,
+                cache_type,
+                size_bytes,
+                line_size,
+            });
+        }
+    }
+    out
+}
+
+fn read_u64_file(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Parses sysfs cache `size` files, which are a number followed by `K`/`M`
+/// (e.g. `"32K"`, `"1M"`), or occasionally a bare byte count.
+fn parse_sysfs_size(s: &str) -> Option<u64> {
+    if let Some(num) = s.strip_suffix('K') {
+        num.parse::<u64>().ok().map(|n| n * 1024)
+    } else if let Some(num) = s.strip_suffix('M') {
+        num.parse::<u64>().ok().map(|n| n * 1024 * 1024)
+    } else {
+        s.parse().ok()
+    }
+}
+
+fn sysfs_cache_size(level: u64, cache_type: &str) -> Option<u64> {
+    read_sysfs_cache_entries()
+        .into_iter()
+        .find(|e| e.level == level && e.cache_type == cache_type)
+        .map(|e| e.size_bytes)
+}
+
+fn sysfs_line_size() -> Option<u64> {
+    read_sysfs_cache_entries()
+        .into_iter()
+        .find(|e| e.line_size > 0)
+        .map(|e| e.line_size)
+}
+
+// ─── cpuid fallback ─────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy)]
+enum CpuidCacheKind {
+    L1d,
+    L1i,
+    L2,
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod cpuid {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::{CpuidResult, __cpuid, __cpuid_count};
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{CpuidResult, __cpuid, __cpuid_count};
+
+    #[inline]
+    pub fn cpuid(leaf: u32, subleaf: u32) -> CpuidResult {
+        __cpuid_count(leaf, subleaf)
+    }
+
+    #[inline]
+    pub fn max_leaf(leaf: u32) -> u32 {
+        __cpuid(leaf).eax
+    }
+}
+
+/// Deterministic cache parameters via CPUID leaf 4: subleaf `i` describes
+/// one cache level/type, terminated by a subleaf whose cache type (EAX
+/// bits 4:0) is 0. Size in bytes is `(ways+1) * (partitions+1) *
+/// (line_size+1) * (sets+1)`, each field packed 1-less-than-actual.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn cpuid_cache_size(kind: CpuidCacheKind) -> Option<u64> {
+    if cpuid::max_leaf(0) < 4 {
+        return None;
+    }
+    for subleaf in 0..32 {
+        let r = cpuid::cpuid(4, subleaf);
+        let cache_type = r.eax & 0x1f;
+        if cache_type == 0 {
+            break;
+        }
+        let level = (r.eax >> 5) & 0x7;
+        let is_data = cache_type == 1;
+        let is_instruction = cache_type == 2;
+        let is_unified = cache_type == 3;
+        let matches = match kind {
+            CpuidCacheKind::L1d => level == 1 && (is_data || is_unified),
+            CpuidCacheKind::L1i => level == 1 && is_instruction,
+            CpuidCacheKind::L2 => level == 2 && (is_unified || is_data),
+        };
+        if !matches {
+            continue;
+        }
+        let line_size = (r.ebx & 0xfff) + 1;
+        let partitions = ((r.ebx >> 12) & 0x3ff) + 1;
+        let ways = ((r.ebx >> 22) & 0x3ff) + 1;
+        let sets = r.ecx + 1;
+        return Some(line_size as u64 * partitions as u64 * ways as u64 * sets as u64);
+    }
+    None
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn cpuid_cache_size(_kind: CpuidCacheKind) -> Option<u64> {
+    None
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn cpuid_line_size() -> Option<u64> {
+    if cpuid::max_leaf(0) < 4 {
+        return None;
+    }
+    let r = cpuid::cpuid(4, 0);
+    if r.eax & 0x1f == 0 {
+        return None;
+    }
+    Some((r.ebx & 0xfff) as u64 + 1)
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn cpuid_line_size() -> Option<u64> {
+    None
+}
+
+/// Brand string via CPUID leaves 0x80000002-0x80000004: each leaf returns
+/// 16 ASCII bytes (EAX/EBX/ECX/EDX, little-endian), three leaves
+/// concatenated giving the full (NUL-padded) 48-byte string.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn cpuid_brand_string() -> Option<String> {
+    if cpuid::max_leaf(0x8000_0000) < 0x8000_0004 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(48);
+    for leaf in 0x8000_0002..=0x8000_0004u32 {
+        let r = cpuid::cpuid(leaf, 0);
+        for reg in [r.eax, r.ebx, r.ecx, r.edx] {
+            bytes.extend_from_slice(&reg.to_le_bytes());
+        }
+    }
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    String::from_utf8(bytes).ok().map(|s| s.trim().to_string())
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn cpuid_brand_string() -> Option<String> {
+    None
+}
@@ -0,0 +1,272 @@
+//! Log-linear latency histogram for coordinated-omission-aware recording.
+//!
+//! `measure_batched` times a whole batch and divides by `batch_size`, which
+//! collapses every per-op latency into a batch average — fine for a rough
+//! throughput number, but it destroys the tail a p99 is supposed to capture.
+//! `LatencyHistogram` instead buckets each individual latency in O(1) time
+//! and O(1) memory (independent of sample count), using the same
+//! exponent-plus-linear-sub-bucket scheme as `lithos_perf_recorder`'s
+//! histogram storage mode: the top set bit of a value picks a row, and
+//! `SUB_BUCKET_BITS` bits below it pick a sub-bucket, bounding relative
+//! error to about `1 / 2^SUB_BUCKET_BITS` regardless of magnitude.
+
+use crate::Stats;
+
+/// Bits of linear resolution kept below the leading bit of each power-of-two
+/// exponent. `2^11 = 2048` sub-buckets gives about 3 significant figures of
+/// precision, matching the usual HDR histogram default.
+const SUB_BUCKET_BITS: u32 = 11;
+const SUB_BUCKET_COUNT: usize = 1 << SUB_BUCKET_BITS;
+/// One row per bit of `u64`, so the histogram covers the full value range.
+const NUM_EXPONENTS: usize = 64;
+/// Below this value, the leading bit is too low to leave `SUB_BUCKET_BITS` of
+/// room beneath it, so the log-linear scheme below has nothing to key
+/// sub-bucket resolution off of. Standard HdrHistogram low-range handling:
+/// give every integer below here (`2 * SUB_BUCKET_COUNT` of them) its own
+/// bucket instead of collapsing a whole exponent row into one.
+const LOW_RANGE_BITS: u32 = SUB_BUCKET_BITS + 1;
+const LOW_RANGE_VALUES: usize = 1 << LOW_RANGE_BITS;
+const NUM_BUCKETS: usize =
+    LOW_RANGE_VALUES + (NUM_EXPONENTS - LOW_RANGE_BITS as usize) * SUB_BUCKET_COUNT;
+
+/// Maps a value to its bucket. Values below `LOW_RANGE_VALUES` get unit
+/// resolution (one bucket per integer); wider values use the log-linear
+/// scheme, where the top bit picks the exponent row and the next
+/// `SUB_BUCKET_BITS` bits below it pick the sub-bucket.
+fn bucket_index(value: u64) -> usize {
+    let v = value.max(1);
+    if v < LOW_RANGE_VALUES as u64 {
+        return v as usize;
+    }
+    let exp = 63 - v.leading_zeros();
+    let shift = exp - SUB_BUCKET_BITS;
+    let sub = ((v >> shift) & (SUB_BUCKET_COUNT as u64 - 1)) as usize;
+    LOW_RANGE_VALUES + (exp - LOW_RANGE_BITS) as usize * SUB_BUCKET_COUNT + sub
+}
+
+/// Midpoint of the value range a bucket covers — the representative value
+/// `percentile()` returns for that bucket. Inverse of `bucket_index`.
+fn bucket_midpoint(idx: usize) -> u64 {
+    if idx < LOW_RANGE_VALUES {
+        return idx as u64;
+    }
+    let rel = idx - LOW_RANGE_VALUES;
+    let exp = LOW_RANGE_BITS + (rel / SUB_BUCKET_COUNT) as u32;
+    let sub = rel % SUB_BUCKET_COUNT;
+    let shift = exp - SUB_BUCKET_BITS;
+    let width = 1u64 << shift;
+    let lo = (1u64 << exp) | ((sub as u64) << shift);
+    lo + width / 2
+}
+
+/// Fixed-memory, log-linear histogram of per-op latencies in nanoseconds.
+pub struct LatencyHistogram {
+    buckets: Box<[u64; NUM_BUCKETS]>,
+    count: u64,
+    min: u64,
+    max: u64,
+    /// Exact running sum, kept alongside the bucketed counts so `mean` stays
+    /// precise even though individual samples aren't retained. `u128` because
+    /// a soak run recording for minutes can overflow a `u64` sum.
+    sum: u128,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![0u64; NUM_BUCKETS].into_boxed_slice().try_into().ok().unwrap(),
+            count: 0,
+            min: u64::MAX,
+            max: 0,
+            sum: 0,
+        }
+    }
+
+    /// Records a single observed latency.
+    #[inline]
+    pub fn record(&mut self, latency_ns: u64) {
+        self.buckets[bucket_index(latency_ns)] += 1;
+        self.count += 1;
+        self.min = self.min.min(latency_ns);
+        self.max = self.max.max(latency_ns);
+        self.sum += latency_ns as u128;
+    }
+
+    /// Records `latency_ns`, then corrects for coordinated omission: if the
+    /// op took longer than `expected_interval_ns`, the stall also delayed
+    /// every subsequent op that would otherwise have been issued during it,
+    /// and those never show up as samples on their own. This synthesizes
+    /// phantom samples at `expected_interval_ns`-sized steps down from the
+    /// observed latency (matching HdrHistogram's
+    /// `recordValueWithExpectedInterval`), so the stall's effect on the tail
+    /// isn't erased just because only one op happened to be in flight when
+    /// it hit.
+    pub fn record_with_expected_interval(&mut self, latency_ns: u64, expected_interval_ns: u64) {
+        self.record(latency_ns);
+        if expected_interval_ns == 0 {
+            return;
+        }
+        let mut missing = latency_ns.saturating_sub(expected_interval_ns);
+        while missing >= expected_interval_ns {
+            self.record(missing);
+            missing -= expected_interval_ns;
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn min(&self) -> u64 {
+        if self.count == 0 { 0 } else { self.min }
+    }
+
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Value at percentile `q` (`0.0..=100.0`), found by summing bucket
+    /// counts left-to-right until the target rank is reached.
+    pub fn percentile(&self, q: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        if q >= 100.0 {
+            return self.max;
+        }
+        let target = ((q / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut cumulative = 0u64;
+        for (idx, &c) in self.buckets.iter().enumerate() {
+            cumulative += c;
+            if cumulative >= target {
+                return bucket_midpoint(idx).min(self.max);
+            }
+        }
+        self.max
+    }
+
+    /// Percentiles read back out as a `Stats`, for the same table/JSON
+    /// output `compute_stats` produces from raw samples. `mean` comes from
+    /// the exact running sum; `stddev` and the bootstrap CI fields aren't
+    /// meaningful for bucketed data (the individual samples aren't
+    /// retained) and are left zeroed, same as `report::histogram_stats`
+    /// does for `PerfRecorder`'s histogram mode.
+    pub fn stats(&self) -> Stats {
+        Stats {
+            min: self.min(),
+            max: self.max,
+            mean: if self.count == 0 {
+                0.0
+            } else {
+                self.sum as f64 / self.count as f64
+            },
+            median: self.percentile(50.0),
+            stddev: 0.0,
+            p50: self.percentile(50.0),
+            p75: self.percentile(75.0),
+            p90: self.percentile(90.0),
+            p95: self.percentile(95.0),
+            p99: self.percentile(99.0),
+            p999: self.percentile(99.9),
+            p9999: self.percentile(99.99),
+            p99999: self.percentile(99.999),
+            count: self.count as usize,
+            ci_low: 0,
+            ci_high: 0,
+            ci_level: 0.0,
+            histogram_blob: Some(self.encode_blob()),
+        }
+    }
+
+    /// The full bucketed distribution as `(representative_value, count)`
+    /// pairs, omitting empty buckets — for downstream tooling (e.g. a CDF
+    /// plot) that wants the shape of the whole distribution rather than a
+    /// handful of percentiles.
+    pub fn distribution(&self) -> Vec<(u64, u64)> {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c > 0)
+            .map(|(idx, &c)| (bucket_midpoint(idx), c))
+            .collect()
+    }
+
+    /// Folds `other`'s buckets into `self`. Bucketed histograms are
+    /// additive — summing corresponding buckets from two disjoint sample
+    /// sets gives exactly the histogram that recording both sets into one
+    /// histogram would have produced — so soak windows (or any other two
+    /// runs over the same bucket scheme) can be combined after the fact
+    /// without re-recording from raw samples.
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (dst, src) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            *dst += src;
+        }
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Encodes the nonzero `(bucket_index, count)` pairs as a compact,
+    /// self-contained blob for the JSON report: bucket indices are
+    /// delta-encoded (they're emitted in increasing order, so the deltas
+    /// are small) and both the delta and the count are written as LEB128
+    /// unsigned varints, then the whole byte stream is base64-encoded.
+    /// Hand-rolled rather than pulling in a varint/base64 crate, matching
+    /// how the rest of this tool avoids new dependencies for small
+    /// encodings (see `splitmix64` in `lib.rs`).
+    pub fn encode_blob(&self) -> String {
+        let mut bytes = Vec::new();
+        let mut prev_idx = 0u64;
+        for (idx, &c) in self.buckets.iter().enumerate() {
+            if c == 0 {
+                continue;
+            }
+            let idx = idx as u64;
+            write_varint(&mut bytes, idx - prev_idx);
+            write_varint(&mut bytes, c);
+            prev_idx = idx;
+        }
+        base64_encode(&bytes)
+    }
+}
+
+/// Writes `value` as an LEB128 unsigned varint: 7 bits of payload per byte,
+/// high bit set on every byte but the last.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard (padded) base64 encoding — just enough to make `encode_blob`'s
+/// byte stream safe to embed as a JSON string without a new dependency.
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        let n = (b0 as u32) << 16 | (b1 as u32) << 8 | b2 as u32;
+        out.push(BASE64_ALPHABET[(n >> 18 & 0x3f) as usize] as char);
+        out.push(BASE64_ALPHABET[(n >> 12 & 0x3f) as usize] as char);
+        out.push(if chunk.len() > 1 { BASE64_ALPHABET[(n >> 6 & 0x3f) as usize] as char } else { '=' });
+        out.push(if chunk.len() > 2 { BASE64_ALPHABET[(n & 0x3f) as usize] as char } else { '=' });
+    }
+    out
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -4,6 +4,16 @@ use std::ffi::CString;
 use std::path::Path;
 use std::sync::OnceLock;
 use std::time::Instant;
+use sysinfo::System;
+
+mod histogram;
+pub use histogram::LatencyHistogram;
+
+#[cfg(all(target_os = "linux", not(target_vendor = "apple")))]
+mod cache_info_linux;
+
+mod perf_counters;
+pub use perf_counters::{CounterBenchResult, PerfCounterTotals, PerfCounters, measure_with_counters};
 
 // ─── Statistics ─────────────────────────────────────────────────────────────
 
@@ -21,10 +31,26 @@ pub struct Stats {
     pub p99: u64,
     pub p999: u64,
     pub p9999: u64,
+    pub p99999: u64,
     pub count: usize,
+
+    /// Bootstrap-resampled confidence interval for `median`, so a p50 move
+    /// between runs can be judged against noise rather than read as a point
+    /// estimate. See `bootstrap_ci`.
+    pub ci_low: u64,
+    pub ci_high: u64,
+    pub ci_level: f64,
+
+    /// Varint-delta + base64 encoding of the full bucketed distribution
+    /// (see `LatencyHistogram::encode_blob`), for downstream tooling that
+    /// wants to reconstruct a CDF rather than read off fixed percentiles.
+    /// `None` where the samples were never bucketed (e.g. `PerfRecorder`'s
+    /// own histogram mode, read back through `report::histogram_stats`,
+    /// which has percentiles but not the raw bucket counts).
+    pub histogram_blob: Option<String>,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BenchResult {
     pub name: String,
     pub unit: String,
@@ -49,6 +75,14 @@ pub fn compute_stats(samples: &mut [u64]) -> Stats {
         / count as f64;
     let stddev = variance.sqrt();
 
+    let (ci_low, ci_high) = bootstrap_ci(
+        samples,
+        DEFAULT_BOOTSTRAP_RESAMPLES,
+        DEFAULT_CI_LEVEL,
+        default_bootstrap_seed(),
+        median_of,
+    );
+
     Stats {
         min: samples[0],
         max: samples[count - 1],
@@ -62,8 +96,24 @@ pub fn compute_stats(samples: &mut [u64]) -> Stats {
         p99: percentile_sorted(samples, 99.0),
         p999: percentile_sorted(samples, 99.9),
         p9999: percentile_sorted(samples, 99.99),
+        p99999: percentile_sorted(samples, 99.999),
         count,
+        ci_low,
+        ci_high,
+        ci_level: DEFAULT_CI_LEVEL,
+        histogram_blob: Some(histogram_blob_of(samples)),
+    }
+}
+
+/// Buckets `sorted` into a throwaway `LatencyHistogram` just to reuse its
+/// `encode_blob` — cheaper than carrying two separate bucketing schemes for
+/// the raw-samples and live-histogram recording paths.
+fn histogram_blob_of(sorted: &[u64]) -> String {
+    let mut hist = LatencyHistogram::new();
+    for &s in sorted {
+        hist.record(s);
     }
+    hist.encode_blob()
 }
 
 fn percentile_sorted(sorted: &[u64], pct: f64) -> u64 {
@@ -76,6 +126,74 @@ fn percentile_sorted(sorted: &[u64], pct: f64) -> u64 {
     sorted[idx]
 }
 
+fn median_of(unsorted: &[u64]) -> u64 {
+    let mut tmp = unsorted.to_vec();
+    tmp.sort_unstable();
+    percentile_sorted(&tmp, 50.0)
+}
+
+// ─── Bootstrap Confidence Intervals ─────────────────────────────────────────
+
+pub const DEFAULT_BOOTSTRAP_RESAMPLES: usize = 1000;
+pub const DEFAULT_CI_LEVEL: f64 = 0.95;
+
+/// SplitMix64: a fast, seeded PRNG. Not for anything security-sensitive —
+/// just deterministic-given-a-seed and cheap enough to drive thousands of
+/// bootstrap resamples without becoming the bottleneck it's measuring around.
+#[inline]
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Varies run to run (so repeated `compute_stats` calls don't draw identical
+/// resamples) without pulling in an OS RNG dependency for a non-cryptographic
+/// seed.
+fn default_bootstrap_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let mut s = nanos ^ 0xD1B54A32D192ED03;
+    splitmix64(&mut s)
+}
+
+/// Bootstrap-resampled confidence interval for `statistic` over `samples`.
+///
+/// Draws `resamples` samples-with-replacement of `samples.len()`, applies
+/// `statistic` to each draw, sorts the resulting distribution, and returns
+/// its `(1-level)/2` / `1-(1-level)/2` percentiles as the interval bounds —
+/// e.g. for `level = 0.95` that's the 2.5th/97.5th percentiles.
+pub fn bootstrap_ci(
+    samples: &[u64],
+    resamples: usize,
+    level: f64,
+    seed: u64,
+    statistic: impl Fn(&[u64]) -> u64,
+) -> (u64, u64) {
+    assert!(!samples.is_empty(), "cannot bootstrap an empty sample");
+    let n = samples.len();
+    let mut state = seed;
+    let mut draw = vec![0u64; n];
+    let mut boot = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        for slot in draw.iter_mut() {
+            let idx = (splitmix64(&mut state) as usize) % n;
+            *slot = samples[idx];
+        }
+        boot.push(statistic(&draw));
+    }
+    boot.sort_unstable();
+    let tail = (1.0 - level) / 2.0 * 100.0;
+    (
+        percentile_sorted(&boot, tail),
+        percentile_sorted(&boot, 100.0 - tail),
+    )
+}
+
 // ─── Measurement Harness ────────────────────────────────────────────────────
 
 pub fn measure_batched<F: FnMut()>(
@@ -107,6 +225,61 @@ pub fn measure_batched<F: FnMut()>(
     }
 }
 
+/// Like `measure_batched`, but times every op individually with
+/// `mono_now_ns` and records it into a `LatencyHistogram` instead of
+/// averaging a batch's elapsed time — a p99 computed this way reflects
+/// actual tail latency rather than a smoothed-out batch mean.
+pub fn measure_per_op<F: FnMut()>(name: &str, ops: usize, warmup: usize, mut f: F) -> BenchResult {
+    for _ in 0..warmup {
+        f();
+    }
+
+    let mut hist = LatencyHistogram::new();
+    for _ in 0..ops {
+        let start = mono_now_ns();
+        f();
+        hist.record(mono_now_ns().saturating_sub(start));
+    }
+
+    BenchResult {
+        name: name.to_string(),
+        unit: "ns/op".to_string(),
+        stats: hist.stats(),
+    }
+}
+
+/// Like `measure_per_op`, but applies the coordinated-omission correction:
+/// `expected_interval_ns` is the target spacing between ops (e.g. `1_000_000_000
+/// / target_ops_per_sec`), and any op that overruns it has phantom samples
+/// synthesized for the ops that should have been issued during the stall.
+/// Use this instead of `measure_per_op` when benchmarking against a target
+/// rate rather than closed-loop back-to-back calls.
+pub fn measure_per_op_with_expected_interval<F: FnMut()>(
+    name: &str,
+    ops: usize,
+    warmup: usize,
+    expected_interval_ns: u64,
+    mut f: F,
+) -> BenchResult {
+    for _ in 0..warmup {
+        f();
+    }
+
+    let mut hist = LatencyHistogram::new();
+    for _ in 0..ops {
+        let start = mono_now_ns();
+        f();
+        let elapsed = mono_now_ns().saturating_sub(start);
+        hist.record_with_expected_interval(elapsed, expected_interval_ns);
+    }
+
+    BenchResult {
+        name: name.to_string(),
+        unit: "ns/op".to_string(),
+        stats: hist.stats(),
+    }
+}
+
 // ─── Hardware Info ──────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -118,14 +291,47 @@ pub struct CacheInfo {
     pub ram_bytes: u64,
     pub cpu_brand: String,
     pub ncpu: u64,
-}
-
+    /// Bytes of memory currently free, from `sysinfo` — unlike `ram_bytes`
+    /// (total installed), this one actually changes run to run.
+    pub available_ram_bytes: u64,
+    /// Physical core count, distinct from `ncpu` (logical/hyperthreaded).
+    pub physical_cores: u64,
+    pub os_version: String,
+    pub kernel_version: String,
+}
+
+/// Populates `CacheInfo` from `sysinfo`'s one-shot `System::new_all()` +
+/// `refresh_all()` — CPU brand, core/thread counts, memory, and OS/kernel
+/// version all come back from a single portable call instead of shelling
+/// out to `uname`/`sysctl`/`lscpu`. `sysinfo` has no notion of per-level
+/// cache sizes, though, so the L1/L2/cache-line fields still come from the
+/// platform-specific probes below (`sysctlbyname` on Apple,
+/// `/sys/devices/system/cpu` on Linux) — sysinfo's CPU brand/RAM are used
+/// as the primary source with the old probes kept as a fallback for
+/// whatever platform sysinfo doesn't recognize.
 pub fn get_cache_info() -> CacheInfo {
-    let ncpu = std::thread::available_parallelism()
-        .map(|n| n.get() as u64)
-        .unwrap_or(0);
-    let ram_bytes = total_ram_bytes().unwrap_or(0);
-    let cpu_brand = cpu_brand_string().unwrap_or_else(|| "unknown".into());
+    let mut sys = System::new_all();
+    sys.refresh_all();
+
+    let ncpu = sys.cpus().len() as u64;
+    let physical_cores = System::physical_core_count()
+        .map(|n| n as u64)
+        .unwrap_or(ncpu);
+    let cpu_brand = sys
+        .cpus()
+        .first()
+        .map(|c| c.brand().trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(cpu_brand_string)
+        .unwrap_or_else(|| "unknown".into());
+    let ram_bytes = match sys.total_memory() {
+        0 => total_ram_bytes().unwrap_or(0),
+        bytes => bytes,
+    };
+    let available_ram_bytes = sys.available_memory();
+    let os_version = System::long_os_version().unwrap_or_else(|| "unknown".into());
+    let kernel_version = System::kernel_version().unwrap_or_else(|| "unknown".into());
+
     let line_size =
         cacheline_bytes().unwrap_or_else(|| if cpu_brand.contains("Apple") { 128 } else { 64 });
 
@@ -137,6 +343,10 @@ pub fn get_cache_info() -> CacheInfo {
         ram_bytes,
         cpu_brand,
         ncpu,
+        available_ram_bytes,
+        physical_cores,
+        os_version,
+        kernel_version,
     }
 }
 
@@ -145,7 +355,12 @@ fn l1d_cache_bytes() -> Option<u64> {
     sysctl_u64("hw.perflevel0.l1dcachesize").or_else(|| sysctl_u64("hw.l1dcachesize"))
 }
 
-#[cfg(not(target_vendor = "apple"))]
+#[cfg(all(target_os = "linux", not(target_vendor = "apple")))]
+fn l1d_cache_bytes() -> Option<u64> {
+    cache_info_linux::l1d_cache_bytes()
+}
+
+#[cfg(not(any(target_vendor = "apple", target_os = "linux")))]
 fn l1d_cache_bytes() -> Option<u64> {
     None
 }
@@ -155,7 +370,12 @@ fn l1i_cache_bytes() -> Option<u64> {
     sysctl_u64("hw.perflevel0.l1icachesize").or_else(|| sysctl_u64("hw.l1icachesize"))
 }
 
-#[cfg(not(target_vendor = "apple"))]
+#[cfg(all(target_os = "linux", not(target_vendor = "apple")))]
+fn l1i_cache_bytes() -> Option<u64> {
+    cache_info_linux::l1i_cache_bytes()
+}
+
+#[cfg(not(any(target_vendor = "apple", target_os = "linux")))]
 fn l1i_cache_bytes() -> Option<u64> {
     None
 }
@@ -165,7 +385,12 @@ fn l2_cache_bytes() -> Option<u64> {
     sysctl_u64("hw.perflevel0.l2cachesize").or_else(|| sysctl_u64("hw.l2cachesize"))
 }
 
-#[cfg(not(target_vendor = "apple"))]
+#[cfg(all(target_os = "linux", not(target_vendor = "apple")))]
+fn l2_cache_bytes() -> Option<u64> {
+    cache_info_linux::l2_cache_bytes()
+}
+
+#[cfg(not(any(target_vendor = "apple", target_os = "linux")))]
 fn l2_cache_bytes() -> Option<u64> {
     None
 }
@@ -175,7 +400,12 @@ fn cacheline_bytes() -> Option<u64> {
     sysctl_u64("hw.cachelinesize")
 }
 
-#[cfg(not(target_vendor = "apple"))]
+#[cfg(all(target_os = "linux", not(target_vendor = "apple")))]
+fn cacheline_bytes() -> Option<u64> {
+    cache_info_linux::cacheline_bytes()
+}
+
+#[cfg(not(any(target_vendor = "apple", target_os = "linux")))]
 fn cacheline_bytes() -> Option<u64> {
     None
 }
@@ -185,7 +415,12 @@ fn total_ram_bytes() -> Option<u64> {
     sysctl_u64("hw.memsize")
 }
 
-#[cfg(not(target_vendor = "apple"))]
+#[cfg(all(target_os = "linux", not(target_vendor = "apple")))]
+fn total_ram_bytes() -> Option<u64> {
+    cache_info_linux::total_ram_bytes()
+}
+
+#[cfg(not(any(target_vendor = "apple", target_os = "linux")))]
 fn total_ram_bytes() -> Option<u64> {
     None
 }
@@ -197,7 +432,12 @@ fn cpu_brand_string() -> Option<String> {
         .or_else(|| sysctl_str("hw.machine"))
 }
 
-#[cfg(not(target_vendor = "apple"))]
+#[cfg(all(target_os = "linux", not(target_vendor = "apple")))]
+fn cpu_brand_string() -> Option<String> {
+    cache_info_linux::cpu_brand_string()
+}
+
+#[cfg(not(any(target_vendor = "apple", target_os = "linux")))]
 fn cpu_brand_string() -> Option<String> {
     None
 }
@@ -275,6 +515,22 @@ pub struct ResourceSnapshot {
     pub invol_ctx_switches: i64,
     pub user_time_us: i64,
     pub sys_time_us: i64,
+
+    /// Cumulative `some avg*` `total` stall microseconds read from
+    /// `/proc/pressure/{cpu,memory,io}`. `None` off Linux, or if PSI is
+    /// disabled/unreadable (e.g. missing `CONFIG_PSI`, no permissions).
+    pub psi_cpu_stall_us: Option<u64>,
+    pub psi_mem_stall_us: Option<u64>,
+    pub psi_io_stall_us: Option<u64>,
+
+    /// cgroup v2 `memory.current` / `memory.peak` for this process's
+    /// cgroup. `None` off Linux or outside a cgroup v2 hierarchy.
+    pub cgroup_memory_current_bytes: Option<u64>,
+    pub cgroup_memory_peak_bytes: Option<u64>,
+
+    /// Sum of `rbytes`/`wbytes` across devices from cgroup v2 `io.stat`.
+    pub cgroup_io_rbytes: Option<u64>,
+    pub cgroup_io_wbytes: Option<u64>,
 }
 
 pub fn capture_rusage() -> ResourceSnapshot {
@@ -292,9 +548,107 @@ pub fn capture_rusage() -> ResourceSnapshot {
         invol_ctx_switches: usage.ru_nivcsw,
         user_time_us: usage.ru_utime.tv_sec * 1_000_000 + usage.ru_utime.tv_usec as i64,
         sys_time_us: usage.ru_stime.tv_sec * 1_000_000 + usage.ru_stime.tv_usec as i64,
+        psi_cpu_stall_us: psi_some_total_us("cpu"),
+        psi_mem_stall_us: psi_some_total_us("memory"),
+        psi_io_stall_us: psi_some_total_us("io"),
+        cgroup_memory_current_bytes: cgroup_file_u64("memory.current"),
+        cgroup_memory_peak_bytes: cgroup_file_u64("memory.peak"),
+        cgroup_io_rbytes: cgroup_io_stat_sum("rbytes"),
+        cgroup_io_wbytes: cgroup_io_stat_sum("wbytes"),
+    }
+}
+
+/// Difference between two `ResourceSnapshot`s taken before/after a measured
+/// window. The `getrusage` and PSI fields are naturally cumulative, so they
+/// subtract cleanly into a delta; `peak_rss_cgroup_bytes` is just the later
+/// snapshot's `memory.peak`, since that counter is already a running max.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ResourceDelta {
+    pub max_rss_bytes: i64,
+    pub minor_faults: i64,
+    pub major_faults: i64,
+    pub user_time_us: i64,
+    pub sys_time_us: i64,
+    pub cpu_stall_us: Option<i64>,
+    pub mem_stall_us: Option<i64>,
+    pub io_stall_us: Option<i64>,
+    pub peak_rss_cgroup_bytes: Option<u64>,
+}
+
+pub fn resource_delta(before: &ResourceSnapshot, after: &ResourceSnapshot) -> ResourceDelta {
+    fn sub_opt(after: Option<u64>, before: Option<u64>) -> Option<i64> {
+        Some(after? as i64 - before? as i64)
+    }
+    ResourceDelta {
+        max_rss_bytes: after.max_rss_bytes - before.max_rss_bytes,
+        minor_faults: after.minor_faults - before.minor_faults,
+        major_faults: after.major_faults - before.major_faults,
+        user_time_us: after.user_time_us - before.user_time_us,
+        sys_time_us: after.sys_time_us - before.sys_time_us,
+        cpu_stall_us: sub_opt(after.psi_cpu_stall_us, before.psi_cpu_stall_us),
+        mem_stall_us: sub_opt(after.psi_mem_stall_us, before.psi_mem_stall_us),
+        io_stall_us: sub_opt(after.psi_io_stall_us, before.psi_io_stall_us),
+        peak_rss_cgroup_bytes: after.cgroup_memory_peak_bytes,
     }
 }
 
+#[cfg(target_os = "linux")]
+fn psi_some_total_us(resource: &str) -> Option<u64> {
+    let data = std::fs::read_to_string(format!("/proc/pressure/{resource}")).ok()?;
+    let some_line = data.lines().find(|l| l.starts_with("some "))?;
+    let total = some_line
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("total="))?;
+    total.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn psi_some_total_us(_resource: &str) -> Option<u64> {
+    None
+}
+
+/// This process's cgroup v2 directory under the unified hierarchy, resolved
+/// from the single `0::<path>` line in `/proc/self/cgroup`.
+#[cfg(target_os = "linux")]
+fn cgroup_path() -> Option<std::path::PathBuf> {
+    let data = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let rel = data.lines().find_map(|l| l.strip_prefix("0::"))?;
+    Some(std::path::Path::new("/sys/fs/cgroup").join(rel.trim_start_matches('/')))
+}
+
+#[cfg(target_os = "linux")]
+fn cgroup_file_u64(file: &str) -> Option<u64> {
+    let data = std::fs::read_to_string(cgroup_path()?.join(file)).ok()?;
+    data.trim().parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cgroup_file_u64(_file: &str) -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn cgroup_io_stat_sum(field: &str) -> Option<u64> {
+    let data = std::fs::read_to_string(cgroup_path()?.join("io.stat")).ok()?;
+    let prefix = format!("{field}=");
+    let mut total = 0u64;
+    let mut found = false;
+    for part in data.split_whitespace() {
+        if let Some(v) = part.strip_prefix(&prefix)
+            && let Ok(n) = v.parse::<u64>()
+        {
+            total += n;
+            found = true;
+        }
+    }
+    found.then_some(total)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cgroup_io_stat_sum(_field: &str) -> Option<u64> {
+    None
+}
+
 // ─── Helpers ────────────────────────────────────────────────────────────────
 
 pub fn temp_shm_path(label: &str) -> String {
@@ -385,6 +739,15 @@ pub fn print_result_row(r: &BenchResult) {
         r.stats.max,
         r.unit,
     );
+    if r.stats.ci_level > 0.0 {
+        println!(
+            "  {:<30} median {:>.0}% CI: [{}, {}]",
+            "",
+            r.stats.ci_level * 100.0,
+            r.stats.ci_low,
+            r.stats.ci_high,
+        );
+    }
 }
 
 pub fn print_table_header() {
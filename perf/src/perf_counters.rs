@@ -0,0 +1,293 @@
+//! Linux hardware performance counters (`perf_event_open(2)`) for attributing
+//! latency to cache misses / branch mispredictions instead of only timing
+//! the region. Opens a counter group — cycles as the group leader,
+//! instructions / LLC cache misses / branch mispredictions as members —
+//! resets and enables it around the measured run, and reads the grouped
+//! totals back with a single `read()`.
+//!
+//! Requires `CAP_PERFMON` (or a permissive `perf_event_paranoid`) and Linux.
+//! When the leader can't be opened, `PerfCounters::open()` returns `None`
+//! and callers fall back to timing-only results rather than aborting the
+//! benchmark.
+
+use crate::{Stats, compute_stats, mono_now_ns};
+
+/// Aggregated counter values for one measured run, in the same units the
+/// kernel reports them: raw cycle/instruction/event counts, not rates.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct PerfCounterTotals {
+    pub cycles: u64,
+    pub instructions: u64,
+    pub cache_misses: u64,
+    pub branch_misses: u64,
+}
+
+impl PerfCounterTotals {
+    pub fn ipc(&self) -> f64 {
+        if self.cycles == 0 {
+            0.0
+        } else {
+            self.instructions as f64 / self.cycles as f64
+        }
+    }
+
+    pub fn cache_misses_per_op(&self, ops: usize) -> f64 {
+        if ops == 0 { 0.0 } else { self.cache_misses as f64 / ops as f64 }
+    }
+
+    pub fn branch_misses_per_op(&self, ops: usize) -> f64 {
+        if ops == 0 { 0.0 } else { self.branch_misses as f64 / ops as f64 }
+    }
+}
+
+/// Result of `measure_with_counters`: timing `Stats` alongside whatever
+/// hardware counters were available. `counters` is `None` wherever
+/// `PerfCounters::open()` couldn't open the group.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CounterBenchResult {
+    pub name: String,
+    pub unit: String,
+    pub stats: Stats,
+    pub counters: Option<PerfCounterTotals>,
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::PerfCounterTotals;
+    use std::os::fd::RawFd;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    /// `read()` format for the group leader: a leading `nr` followed by
+    /// `nr` raw `u64` values, one per fd in the group, no per-value id/time.
+    const PERF_FORMAT_GROUP: u64 = 1 << 3;
+
+    const PERF_EVENT_IOC_ENABLE: u64 = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: u64 = 0x2401;
+    const PERF_EVENT_IOC_RESET: u64 = 0x2403;
+    const PERF_IOC_FLAG_GROUP: u64 = 1;
+
+    const ATTR_DISABLED: u64 = 1 << 0;
+    const ATTR_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const ATTR_EXCLUDE_HV: u64 = 1 << 6;
+
+    /// `struct perf_event_attr` from `linux/perf_event.h`. The kernel ABI
+    /// packs a block of single-bit flags into one `u64` (`flags` here,
+    /// `disabled`/`exclude_kernel`/`exclude_hv`/... in the C struct); we
+    /// only ever set the three bits above so there's no need to model the
+    /// rest individually.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        type_: u32,
+        size: u32,
+        config: u64,
+        sample_period_or_freq: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events_or_watermark: u32,
+        bp_type: u32,
+        config1_or_bp_addr: u64,
+        config2_or_bp_len: u64,
+        branch_sample_type: u64,
+        sample_regs_user: u64,
+        sample_stack_size: u32,
+        clockid: i32,
+        sample_regs_intr: u64,
+        aux_watermark: u32,
+        sample_max_stack: u16,
+        reserved_2: u16,
+        aux_sample_size: u32,
+        reserved_3: u32,
+        sig_data: u64,
+    }
+
+    fn make_attr(config: u64, is_group_leader: bool) -> PerfEventAttr {
+        PerfEventAttr {
+            type_: PERF_TYPE_HARDWARE,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config,
+            flags: ATTR_DISABLED | ATTR_EXCLUDE_KERNEL | ATTR_EXCLUDE_HV,
+            read_format: if is_group_leader { PERF_FORMAT_GROUP } else { 0 },
+            ..Default::default()
+        }
+    }
+
+    /// SAFETY: `attr` is a valid, fully-initialized `perf_event_attr` with
+    /// `size` set to its own size; the syscall only reads it and returns a
+    /// new fd (or a negative errno) with no other memory effects.
+    unsafe fn perf_event_open(attr: &PerfEventAttr, group_fd: RawFd) -> RawFd {
+        unsafe {
+            libc::syscall(
+                libc::SYS_perf_event_open,
+                attr as *const PerfEventAttr,
+                0i32,  // pid: measure the calling thread
+                -1i32, // cpu: any CPU the thread happens to run on
+                group_fd,
+                0u64,
+            ) as RawFd
+        }
+    }
+
+    /// A counter group opened around a region the caller measures directly
+    /// (rather than via `measure_with_counters`'s own timing loop) — reset,
+    /// enable, run the region, disable, read. Used where the measured region
+    /// is driven by code that already exists for other reasons (a consumer
+    /// thread's event loop, a soak loop), so the counters just wrap it
+    /// in-place instead of owning the timing.
+    pub struct PerfCounters {
+        /// `fds[0]` is the group leader (cycles); the rest are whichever of
+        /// instructions/cache-misses/branch-misses actually opened — a
+        /// counter that's unsupported on this CPU is simply dropped instead
+        /// of failing the whole group.
+        fds: Vec<RawFd>,
+    }
+
+    impl PerfCounters {
+        pub fn open() -> Option<Self> {
+            let leader_attr = make_attr(PERF_COUNT_HW_CPU_CYCLES, true);
+            let leader = unsafe { perf_event_open(&leader_attr, -1) };
+            if leader < 0 {
+                return None;
+            }
+
+            let mut fds = vec![leader];
+            for config in [
+                PERF_COUNT_HW_INSTRUCTIONS,
+                PERF_COUNT_HW_CACHE_MISSES,
+                PERF_COUNT_HW_BRANCH_MISSES,
+            ] {
+                let attr = make_attr(config, false);
+                let fd = unsafe { perf_event_open(&attr, leader) };
+                if fd >= 0 {
+                    fds.push(fd);
+                }
+            }
+            Some(Self { fds })
+        }
+
+        fn leader(&self) -> RawFd {
+            self.fds[0]
+        }
+
+        pub fn reset_and_enable(&self) {
+            unsafe {
+                libc::ioctl(self.leader(), PERF_EVENT_IOC_RESET as _, PERF_IOC_FLAG_GROUP);
+                libc::ioctl(self.leader(), PERF_EVENT_IOC_ENABLE as _, PERF_IOC_FLAG_GROUP);
+            }
+        }
+
+        pub fn disable(&self) {
+            unsafe {
+                libc::ioctl(self.leader(), PERF_EVENT_IOC_DISABLE as _, PERF_IOC_FLAG_GROUP);
+            }
+        }
+
+        /// One grouped read: `{ nr: u64, values: [u64; nr] }`, `values` in
+        /// the order the fds were opened. Missing members (because they
+        /// didn't open) just leave the corresponding total at zero.
+        pub fn read_totals(&self) -> PerfCounterTotals {
+            let mut buf = [0u64; 5];
+            let bytes = unsafe {
+                libc::read(
+                    self.leader(),
+                    buf.as_mut_ptr() as *mut libc::c_void,
+                    std::mem::size_of_val(&buf),
+                )
+            };
+            if bytes <= 0 {
+                return PerfCounterTotals::default();
+            }
+            let nr = (buf[0] as usize).min(4);
+            let values = &buf[1..1 + nr];
+            PerfCounterTotals {
+                cycles: values.first().copied().unwrap_or(0),
+                instructions: values.get(1).copied().unwrap_or(0),
+                cache_misses: values.get(2).copied().unwrap_or(0),
+                branch_misses: values.get(3).copied().unwrap_or(0),
+            }
+        }
+    }
+
+    impl Drop for PerfCounters {
+        fn drop(&mut self) {
+            for fd in self.fds.drain(..) {
+                unsafe {
+                    libc::close(fd);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use linux::PerfCounters;
+
+#[cfg(not(target_os = "linux"))]
+pub struct PerfCounters;
+
+#[cfg(not(target_os = "linux"))]
+impl PerfCounters {
+    pub fn open() -> Option<Self> {
+        None
+    }
+
+    pub fn reset_and_enable(&self) {}
+
+    pub fn disable(&self) {}
+
+    pub fn read_totals(&self) -> PerfCounterTotals {
+        PerfCounterTotals::default()
+    }
+}
+
+/// Like `measure_batched`, but also opens the hardware counter group around
+/// the whole measured run (reset once before the first batch, disabled once
+/// after the last) and returns its totals alongside the timing `Stats`.
+/// Falls back to `counters: None` rather than failing the benchmark when
+/// counters can't be opened — off Linux, or `perf_event_paranoid` too
+/// strict / missing `CAP_PERFMON`.
+pub fn measure_with_counters<F: FnMut()>(
+    name: &str,
+    batches: usize,
+    batch_size: usize,
+    warmup: usize,
+    mut f: F,
+) -> CounterBenchResult {
+    for _ in 0..warmup * batch_size {
+        f();
+    }
+
+    let counters = PerfCounters::open();
+    if let Some(c) = &counters {
+        c.reset_and_enable();
+    }
+
+    let mut samples = Vec::with_capacity(batches);
+    for _ in 0..batches {
+        let start = mono_now_ns();
+        for _ in 0..batch_size {
+            f();
+        }
+        let total = mono_now_ns().saturating_sub(start) as u128;
+        let per_op = ((total + (batch_size as u128 / 2)) / batch_size as u128) as u64;
+        samples.push(per_op.max(1));
+    }
+
+    let counter_totals = counters.map(|c| {
+        c.disable();
+        c.read_totals()
+    });
+
+    CounterBenchResult {
+        name: name.to_string(),
+        unit: "ns/op".to_string(),
+        stats: compute_stats(&mut samples),
+        counters: counter_totals,
+    }
+}
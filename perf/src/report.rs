@@ -103,6 +103,85 @@ pub fn print_stage_table(recorder: &PerfRecorder, stages: &[PerfStage], total_st
     }
 }
 
+/// Like `stage_results`, but pulls percentiles straight out of a
+/// histogram-mode `PerfRecorder` (`new_histogram()`) instead of sorting a
+/// sample slice — `mean`/`stddev` aren't meaningful for bucketed data, so
+/// they're left at `0.0`.
+pub fn stage_results_histogram(recorder: &PerfRecorder) -> Vec<BenchResult> {
+    let mut out = Vec::new();
+    for (i, &stage) in ALL_STAGES.iter().enumerate() {
+        let count = recorder.count(stage);
+        if count == 0 {
+            continue;
+        }
+        out.push(BenchResult {
+            name: STAGE_NAMES[i].to_string(),
+            unit: "ns".to_string(),
+            stats: histogram_stats(recorder, stage, count),
+        });
+    }
+    out
+}
+
+fn histogram_stats(recorder: &PerfRecorder, stage: PerfStage, count: usize) -> Stats {
+    Stats {
+        min: 0,
+        max: recorder.max(stage),
+        mean: 0.0,
+        median: recorder.percentile(stage, 50.0),
+        stddev: 0.0,
+        p50: recorder.percentile(stage, 50.0),
+        p75: recorder.percentile(stage, 75.0),
+        p90: recorder.percentile(stage, 90.0),
+        p95: recorder.percentile(stage, 95.0),
+        p99: recorder.percentile(stage, 99.0),
+        p999: recorder.percentile(stage, 99.9),
+        p9999: recorder.percentile(stage, 99.99),
+        p99999: recorder.percentile(stage, 99.999),
+        count,
+        // Bootstrapping needs the raw sample slice, which bucketed
+        // histogram data doesn't retain; left unset like mean/stddev above.
+        ci_low: 0,
+        ci_high: 0,
+        ci_level: 0.0,
+        // PerfRecorder's histogram mode only exposes percentiles, not the
+        // raw bucket counts, so there's nothing to encode a blob from.
+        histogram_blob: None,
+    }
+}
+
+/// Like `print_stage_table`, but reads percentiles from a histogram-mode
+/// recorder instead of sorting each stage's samples.
+pub fn print_stage_table_histogram(recorder: &PerfRecorder, stages: &[PerfStage], total_stage: PerfStage) {
+    println!(
+        "  {:<20} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}  {:>6}",
+        "Stage", "p50", "p90", "p99", "p99.9", "max", "count", "% tot"
+    );
+    println!("  {}", "\u{2500}".repeat(88));
+
+    let total_p50 = recorder.percentile(total_stage, 50.0);
+
+    for &stage in stages {
+        let count = recorder.count(stage);
+        if count == 0 {
+            continue;
+        }
+        let stats = histogram_stats(recorder, stage, count);
+        let pct = if total_p50 > 0 && stage != total_stage {
+            format!("{:.0}%", stats.p50 as f64 / total_p50 as f64 * 100.0)
+        } else if stage == total_stage {
+            "100%".to_string()
+        } else {
+            "-".to_string()
+        };
+        let name = STAGE_NAMES[stage as usize];
+        println!(
+            "  {:<20} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}  {:>6}",
+            name, stats.p50, stats.p90, stats.p99, stats.p999, stats.max, stats.count, pct
+        );
+    }
+}
+
 pub fn print_obsidian_report(recorder: &PerfRecorder) {
     println!("\n  Obsidian Per-Stage Timing:\n");
     print_stage_table(recorder, &OBSIDIAN_STAGES, PerfStage::ObsidianTotal);